@@ -0,0 +1,339 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+import "./Pairing.sol";
+
+/*
+ * Generated by `melon export-batch-verifier`, do not edit by hand.
+ */
+
+/*
+ * Verifies a KZG opening at up to NUM_POINTS points, as made by
+ * `KZGProver::create_batch_witness`, with a single call to the pairing
+ * precompile.
+ */
+contract BatchVerifier {
+    using Pairing for *;
+
+    uint256 constant PRIME_Q =
+        21888242871839275222246405745257275088696311157297823662689037894645226208583;
+    uint256 constant BABYJUB_P =
+        21888242871839275222246405745257275088548364400416034343698204186575808495617;
+
+    uint256 public constant NUM_POINTS = 4;
+
+    /*
+     * Returns true if and only if the committed polynomial takes _values[i]
+     * at _points[i] for every i, i.e. if
+     *     e(proof, [Z(s)]_2) == e(commitment - [I(s)]_1, [1]_2)
+     * where Z vanishes on the points and I interpolates them. Returns false
+     * for duplicate points.
+     * @param _commitment The KZG polynomial commitment.
+     * @param _proof The batch witness.
+     * @param _points The x-values, at least one and at most NUM_POINTS.
+     * @param _values The evaluations at the x-values.
+     */
+    function verifyBatch(
+        Pairing.G1Point memory _commitment,
+        Pairing.G1Point memory _proof,
+        uint256[] memory _points,
+        uint256[] memory _values
+    ) public view returns (bool) {
+        uint256[] memory input = pairingInput(
+            _commitment,
+            _proof,
+            _points,
+            _values
+        );
+        if (input.length == 0) {
+            return false;
+        }
+
+        uint256[1] memory out;
+        bool success;
+        assembly {
+            success := staticcall(
+                sub(gas(), 2000),
+                8,
+                add(input, 0x20),
+                mul(mload(input), 0x20),
+                out,
+                0x20
+            )
+        }
+        require(success, "BatchVerifier: pairing precompile failed");
+
+        return out[0] != 0;
+    }
+
+    /*
+     * @return The input to the pairing precompile for verifyBatch, or an
+     *         empty array for duplicate points. With Z = sum z_j X^j this is
+     *         the n + 1 pairs
+     *             (z_0 proof - commitment + [I(s)]_1, [1]_2), (z_j proof, [s^j]_2)
+     *         whose pairings multiply to 1 if and only if the opening holds.
+     */
+    function pairingInput(
+        Pairing.G1Point memory _commitment,
+        Pairing.G1Point memory _proof,
+        uint256[] memory _points,
+        uint256[] memory _values
+    ) public view returns (uint256[] memory) {
+        uint256 n = _points.length;
+        require(
+            n > 0 && n <= NUM_POINTS,
+            "BatchVerifier: wrong number of points"
+        );
+        require(
+            _values.length == n,
+            "BatchVerifier: points and values differ in length"
+        );
+        require(
+            _commitment.X < PRIME_Q && _commitment.Y < PRIME_Q,
+            "BatchVerifier: _commitment is out of range"
+        );
+        require(
+            _proof.X < PRIME_Q && _proof.Y < PRIME_Q,
+            "BatchVerifier: _proof is out of range"
+        );
+        for (uint256 i = 0; i < n; i++) {
+            require(
+                _points[i] < BABYJUB_P && _values[i] < BABYJUB_P,
+                "BatchVerifier: point or value is out of range"
+            );
+        }
+
+        uint256[] memory z = vanishingPoly(_points);
+        (uint256[] memory interpolation, bool distinct) = interpolate(
+            _points,
+            _values,
+            z
+        );
+        if (!distinct) {
+            return new uint256[](0);
+        }
+
+        // [I(s)]_1 - commitment + z_0 proof
+        Pairing.G1Point memory lhs = Pairing.negate(_commitment);
+        for (uint256 j = 0; j < n; j++) {
+            lhs = Pairing.plus(
+                lhs,
+                Pairing.mulScalar(srsG1(j), interpolation[j])
+            );
+        }
+        lhs = Pairing.plus(lhs, Pairing.mulScalar(_proof, z[0]));
+
+        uint256[] memory input = new uint256[](6 * (n + 1));
+        writePair(input, 0, lhs, srsG2(0));
+        for (uint256 j = 1; j <= n; j++) {
+            writePair(input, j, Pairing.mulScalar(_proof, z[j]), srsG2(j));
+        }
+        return input;
+    }
+
+    /*
+     * @return The coefficients of prod_i (X - _points[i]), lowest first.
+     */
+    function vanishingPoly(uint256[] memory _points)
+        public
+        pure
+        returns (uint256[] memory)
+    {
+        uint256 m = BABYJUB_P;
+        uint256[] memory z = new uint256[](_points.length + 1);
+        z[0] = 1;
+        for (uint256 i = 0; i < _points.length; i++) {
+            // multiply by (X - x_i) in place, from the top
+            uint256 negX = m - _points[i];
+            for (uint256 j = i + 1; j > 0; j--) {
+                z[j] = addmod(z[j - 1], mulmod(negX, z[j], m), m);
+            }
+            z[0] = mulmod(negX, z[0], m);
+        }
+        return z;
+    }
+
+    /*
+     * @return The coefficients of the polynomial of degree < n through the
+     *         points, as sum_i _values[i] q_i / q_i(x_i) with
+     *         q_i = Z / (X - x_i), and whether the points are distinct.
+     */
+    function interpolate(
+        uint256[] memory _points,
+        uint256[] memory _values,
+        uint256[] memory _z
+    ) public view returns (uint256[] memory, bool) {
+        uint256 m = BABYJUB_P;
+        uint256 n = _points.length;
+        uint256[] memory result = new uint256[](n);
+        uint256[] memory q = new uint256[](n);
+
+        for (uint256 i = 0; i < n; i++) {
+            uint256 x = _points[i];
+            // synthetic division of Z by (X - x_i), from the top
+            q[n - 1] = _z[n];
+            for (uint256 j = n - 1; j > 0; j--) {
+                q[j - 1] = addmod(_z[j], mulmod(x, q[j], m), m);
+            }
+
+            // q_i(x_i) = prod_{j != i} (x_i - x_j), zero for duplicates
+            uint256 denominator = 0;
+            for (uint256 j = n; j > 0; j--) {
+                denominator = addmod(mulmod(denominator, x, m), q[j - 1], m);
+            }
+            if (denominator == 0) {
+                return (result, false);
+            }
+
+            uint256 scale = mulmod(_values[i], inverse(denominator), m);
+            for (uint256 j = 0; j < n; j++) {
+                result[j] = addmod(result[j], mulmod(scale, q[j], m), m);
+            }
+        }
+        return (result, true);
+    }
+
+    /*
+     * @return a^(p - 2) mod p, the inverse of a nonzero a, via the modexp
+     *         precompile.
+     */
+    function inverse(uint256 _a) internal view returns (uint256) {
+        uint256[6] memory input = [
+            uint256(32),
+            32,
+            32,
+            _a,
+            BABYJUB_P - 2,
+            BABYJUB_P
+        ];
+        uint256[1] memory out;
+        bool success;
+        assembly {
+            success := staticcall(sub(gas(), 2000), 5, input, 0xc0, out, 0x20)
+        }
+        require(success, "BatchVerifier: modexp precompile failed");
+        return out[0];
+    }
+
+    function writePair(
+        uint256[] memory _input,
+        uint256 _index,
+        Pairing.G1Point memory _p1,
+        Pairing.G2Point memory _p2
+    ) internal pure {
+        uint256 j = _index * 6;
+        _input[j + 0] = _p1.X;
+        _input[j + 1] = _p1.Y;
+        _input[j + 2] = _p2.X[0];
+        _input[j + 3] = _p2.X[1];
+        _input[j + 4] = _p2.Y[0];
+        _input[j + 5] = _p2.Y[1];
+    }
+
+    /*
+     * @return [s^i]_1
+     */
+    function srsG1(uint256 i) internal pure returns (Pairing.G1Point memory) {
+        if (i == 0) {
+            return
+                Pairing.G1Point(
+                    0x0000000000000000000000000000000000000000000000000000000000000001,
+                    0x0000000000000000000000000000000000000000000000000000000000000002
+                );
+        }
+        if (i == 1) {
+            return
+                Pairing.G1Point(
+                    0x1936f7b07be20ac4b7faac53aba252c44112b369f437c12d75b8157882b390aa,
+                    0x055c38c27b1dc7fbbdfbb7b4795e92d0d838126c25b6771908f9a23c35c8921a
+                );
+        }
+        if (i == 2) {
+            return
+                Pairing.G1Point(
+                    0x26e798ae3414f0d8579187ca56053a58db4de965abc1b530777515996e744177,
+                    0x05dff45ccc88f9f61a8a514f98cebdfb30a7b64e2c4f5548fd259810e3c57b70
+                );
+        }
+        if (i == 3) {
+            return
+                Pairing.G1Point(
+                    0x00fb8c0b46d9e9e91060c4c13ebb65b542935cd8fdd22a80acbf0b43af72aab9,
+                    0x05f1a0518bdb33b142526af877ec647cc97ae4ccf42313b21da7642fa281f788
+                );
+        }
+        revert("BatchVerifier: no such G1 power");
+    }
+
+    /*
+     * @return [s^i]_2
+     */
+    function srsG2(uint256 i) internal pure returns (Pairing.G2Point memory) {
+        if (i == 0) {
+            return
+                Pairing.G2Point(
+                    [
+                        uint256(0x198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2),
+                        0x1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed
+                    ],
+                    [
+                        uint256(0x090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b),
+                        0x12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa
+                    ]
+                );
+        }
+        if (i == 1) {
+            return
+                Pairing.G2Point(
+                    [
+                        uint256(0x00fde667faf46ac5c419be1d6f28ff535a43c9efe5600584162084d55d8b508a),
+                        0x070f2ac0bc3263aafb2cae9c281d492b5dfe1573aa83198f8befac6fa375181d
+                    ],
+                    [
+                        uint256(0x06be0ca53e55034aa6719b194db361c07fee1ef3dfdff59c44b80788770c08f2),
+                        0x1e089b71af82470ee99b660d89dcfbdfccc7108e12215ad0fca5d627ebf0bc8c
+                    ]
+                );
+        }
+        if (i == 2) {
+            return
+                Pairing.G2Point(
+                    [
+                        uint256(0x2a5013f6f1a1a3a7cd96b42a51b293d795a206a7fbb9480a1dbf54c06f244e07),
+                        0x0d5df7396a05c1b5f828030f3d5efab0789876b4a63c699d50f0a311b07005c7
+                    ],
+                    [
+                        uint256(0x22ebe0bed9da2e06af3cef8f425fb9650ccf7d5164f864e274d195348192402d),
+                        0x2bba05bac6689373d42ac5582b7d38cd43aa3bbc7ac56b5a8dcc4c8067230619
+                    ]
+                );
+        }
+        if (i == 3) {
+            return
+                Pairing.G2Point(
+                    [
+                        uint256(0x06b6542c41e19b5e23c7df43951341721e946b262ee83fc1f48c92761b79c5d2),
+                        0x06327f5a56d810d1ecbca2f9bcb2885a0c08456fe4dbe9c645fdc00f96b632ec
+                    ],
+                    [
+                        uint256(0x1ca8e3dabd6a813734580dbaea7163f9d07925ae2c963a033ff84867599b0faf),
+                        0x14475b805dc18d886580cedc1ebf9b2b5cb3d18a46ed639ef6a2dfac9bb5c41a
+                    ]
+                );
+        }
+        if (i == 4) {
+            return
+                Pairing.G2Point(
+                    [
+                        uint256(0x0e0c4d3601694d77d7c829c7ca7b5399797717f9168b82aa72199b8e555ef969),
+                        0x2c6787a77a9fd5dd76c50e75ab9930a4360d5093556e78cc43ea59e7a695dfb7
+                    ],
+                    [
+                        uint256(0x22e14b8894d9b2f066a209c0aafb8b69419c5fcebe4729e2c6979dded8c266e3),
+                        0x1f6894f0a597ccb16f120442ea71480dbb60adb60e067e89090047943b7e4ef0
+                    ]
+                );
+        }
+        revert("BatchVerifier: no such G2 power");
+    }
+}
@@ -22,12 +22,11 @@ contract Verifier is Constants {
             Y: [Constants.SRS_G2_Y_0[0], Constants.SRS_G2_Y_1[0]]
         });
 
+    // [s]_2
     Pairing.G2Point SRS_G2_1 =
         Pairing.G2Point({
-            // if not using a dummy ceremony this should index by 1
-            X: [Constants.SRS_G2_X_0[0], Constants.SRS_G2_X_1[0]],
-            // if not using a dummy ceremony this should index by 1
-            Y: [Constants.SRS_G2_Y_0[0], Constants.SRS_G2_Y_1[0]]
+            X: [Constants.SRS_G2_X_0[1], Constants.SRS_G2_X_1[1]],
+            Y: [Constants.SRS_G2_Y_0[1], Constants.SRS_G2_Y_1[1]]
         });
 
     /*
@@ -109,6 +108,10 @@ contract Verifier is Constants {
         view
         returns (Pairing.G1Point memory)
     {
+        require(
+            coefficients.length <= Constants.SRS_G1_X.length,
+            "Verifier.commit: too many coefficients for the SRS"
+        );
         Pairing.G1Point memory result = Pairing.G1Point(0, 0);
 
         for (uint256 i = 0; i < coefficients.length; i++) {
@@ -116,10 +119,8 @@ contract Verifier is Constants {
                 result,
                 Pairing.mulScalar(
                     Pairing.G1Point({
-                        // if not using a dummy ceremony this should index by i
-                        X: Constants.SRS_G1_X[0],
-                         // if not using a dummy ceremony this should index by i
-                        Y: Constants.SRS_G1_Y[0]
+                        X: Constants.SRS_G1_X[i],
+                        Y: Constants.SRS_G1_Y[i]
                     }),
                     coefficients[i]
                 )
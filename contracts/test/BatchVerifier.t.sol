@@ -0,0 +1,140 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+import "forge-std/Test.sol";
+import "../src/BatchVerifier.sol";
+import "../src/Pairing.sol";
+import "forge-std/StdJson.sol";
+import "forge-std/StdUtils.sol";
+
+contract BatchVerifierTest is Test {
+    BatchVerifier public verifier;
+
+    using stdJson for string;
+
+    uint256 constant BABYJUB_P =
+        21888242871839275222246405745257275088548364400416034343698204186575808495617;
+
+    struct Opening {
+        Pairing.G1Point commitment;
+        Pairing.G1Point proof;
+        uint256[] points;
+        uint256[] values;
+    }
+
+    function setUp() public {
+        verifier = new BatchVerifier();
+    }
+
+    // test vectors written by the `solidity` module's tests in the rust crate,
+    // which check the same pairing inputs and results against its verifier
+    function readVectors() internal returns (string memory) {
+        string memory root = vm.projectRoot();
+        return vm.readFile(string.concat(root, "/test/files/batch_vectors.json"));
+    }
+
+    function readOpening(string memory json, string memory key)
+        internal
+        returns (Opening memory)
+    {
+        string memory bundle = string.concat(key, ".bundle");
+        return
+            Opening(
+                Pairing.G1Point(
+                    bytesToUint(json.parseRaw(string.concat(bundle, ".commitment.x"))),
+                    bytesToUint(json.parseRaw(string.concat(bundle, ".commitment.y")))
+                ),
+                Pairing.G1Point(
+                    bytesToUint(json.parseRaw(string.concat(bundle, ".witness.x"))),
+                    bytesToUint(json.parseRaw(string.concat(bundle, ".witness.y")))
+                ),
+                abi.decode(
+                    json.parseRaw(string.concat(bundle, ".points")),
+                    (uint256[])
+                ),
+                abi.decode(
+                    json.parseRaw(string.concat(bundle, ".evaluations")),
+                    (uint256[])
+                )
+            );
+    }
+
+    function testverifyBatch_vectors() public {
+        string memory json = readVectors();
+        uint256 numCases = bytesToUint(json.parseRaw(".num_cases"));
+
+        for (uint256 c = 0; c < numCases; c++) {
+            string memory key = string.concat(".cases[", vm.toString(c), "]");
+            Opening memory opening = readOpening(json, key);
+
+            uint256[] memory expected = abi.decode(
+                json.parseRaw(string.concat(key, ".pairing_input")),
+                (uint256[])
+            );
+            uint256[] memory input = verifier.pairingInput(
+                opening.commitment,
+                opening.proof,
+                opening.points,
+                opening.values
+            );
+            assertEq(input.length, expected.length);
+            for (uint256 i = 0; i < input.length; i++) {
+                assertEq(input[i], expected[i]);
+            }
+
+            bool valid = abi.decode(
+                json.parseRaw(string.concat(key, ".valid")),
+                (bool)
+            );
+            assertEq(
+                verifier.verifyBatch(
+                    opening.commitment,
+                    opening.proof,
+                    opening.points,
+                    opening.values
+                ),
+                valid
+            );
+        }
+    }
+
+    function testverifyBatch_duplicatePoints() public {
+        // the third case opens 4 distinct points
+        Opening memory opening = readOpening(readVectors(), ".cases[2]");
+        opening.points[1] = opening.points[0];
+        opening.values[1] = opening.values[0];
+
+        assertEq(
+            verifier
+                .pairingInput(
+                    opening.commitment,
+                    opening.proof,
+                    opening.points,
+                    opening.values
+                )
+                .length,
+            0
+        );
+        assertEq(
+            verifier.verifyBatch(
+                opening.commitment,
+                opening.proof,
+                opening.points,
+                opening.values
+            ),
+            false
+        );
+    }
+
+    function testvanishingPoly() public {
+        uint256[] memory points = new uint256[](2);
+        points[0] = 2;
+        points[1] = 3;
+        // (X - 2)(X - 3) = X^2 - 5X + 6
+        uint256[] memory z = verifier.vanishingPoly(points);
+        assertEq(z.length, 3);
+        assertEq(z[0], 6);
+        assertEq(z[1], BABYJUB_P - 5);
+        assertEq(z[2], 1);
+    }
+}
@@ -58,6 +58,7 @@ contract KZGVerifierTest is Test {
     }
 
     function testverify_json() public {
+        // proof bundles written by `melon commit` and `melon open`
         string memory root = vm.projectRoot();
         // commit
         string memory pathc = string.concat(
@@ -65,16 +66,16 @@ contract KZGVerifierTest is Test {
             "/test/files/commitment.json"
         );
         string memory jsonc = vm.readFile(pathc);
-        uint256 commitX = bytesToUint(jsonc.parseRaw(".x"));
-        uint256 commitY = bytesToUint(jsonc.parseRaw(".y"));
+        uint256 commitX = bytesToUint(jsonc.parseRaw(".commitment.x"));
+        uint256 commitY = bytesToUint(jsonc.parseRaw(".commitment.y"));
         Pairing.G1Point memory commit = Pairing.G1Point(commitX, commitY);
         // witness
         string memory pathw = string.concat(root, "/test/files/witness.json");
         string memory jsonw = vm.readFile(pathw);
-        uint256 proofX = bytesToUint(jsonw.parseRaw(".x"));
-        uint256 proofY = bytesToUint(jsonw.parseRaw(".y"));
-        uint256 i = bytesToUint(jsonw.parseRaw(".i"));
-        uint256 value = bytesToUint(jsonw.parseRaw(".value"));
+        uint256 proofX = bytesToUint(jsonw.parseRaw(".witness.x"));
+        uint256 proofY = bytesToUint(jsonw.parseRaw(".witness.y"));
+        uint256 i = bytesToUint(jsonw.parseRaw(".points[0]"));
+        uint256 value = bytesToUint(jsonw.parseRaw(".evaluations[0]"));
         Pairing.G1Point memory proof = Pairing.G1Point(proofX, proofY);
         bool res = verifier.verify(commit, proof, i, value);
         assertEq(res, true);
@@ -15,14 +15,14 @@ fn bench_commit<const NUM_COEFFS: usize>(c: &mut Criterion) {
     let params = csprng_setup::<NUM_COEFFS>();
     let mut rng = SmallRng::from_seed([42; 32]);
     let mut coeffs = vec![Scalar::zero(); NUM_COEFFS];
-    for i in 0..NUM_COEFFS {
-        coeffs[i] = rng.gen::<u64>().into();
+    for coeff in coeffs.iter_mut().take(NUM_COEFFS) {
+        *coeff = rng.gen::<u64>().into();
     }
-    let polynomial = Polynomial::new(coeffs);
+    let polynomial = Polynomial::new(coeffs).unwrap();
     let prover = KZGProver::new(&params);
 
     c.bench_function(format!("commit, degree {}", NUM_COEFFS - 1).as_str(), |b| {
-        b.iter(|| black_box(&prover).commit(black_box(&polynomial)))
+        b.iter(|| black_box(&prover).commit(black_box(&polynomial)).unwrap())
     });
 }
 
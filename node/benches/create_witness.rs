@@ -1,5 +1,6 @@
 use ark_bn254::Fr as Scalar;
 use ark_ff::{UniformRand, Zero};
+use melon::kzg::domain::EvaluationDomain;
 use melon::kzg::polynomial::Polynomial;
 use melon::kzg::{setup, KZGParams, KZGProver};
 use rand::{rngs::SmallRng, Rng, SeedableRng};
@@ -20,7 +21,7 @@ fn bench_create_witness<const NUM_COEFFS: usize>(c: &mut Criterion) {
     }
     let polynomial = Polynomial::new_from_coeffs(coeffs, NUM_COEFFS - 1);
     let prover = KZGProver::new(&params);
-    let _commitment = prover.commit(&polynomial);
+    let _commitment = prover.commit(&polynomial).unwrap();
 
     let x: Scalar = Scalar::rand(&mut rng);
     let y = polynomial.eval(x);
@@ -37,9 +38,32 @@ fn bench_create_witness<const NUM_COEFFS: usize>(c: &mut Criterion) {
     );
 }
 
+fn bench_open_all_on_domain<const NUM_COEFFS: usize>(c: &mut Criterion) {
+    let params = csprng_setup::<NUM_COEFFS>();
+    let mut rng = SmallRng::from_seed([42; 32]);
+    let mut coeffs = vec![Scalar::zero(); NUM_COEFFS];
+    for coeff in coeffs.iter_mut().take(NUM_COEFFS) {
+        *coeff = rng.gen::<u64>().into();
+    }
+    let polynomial = Polynomial::new_from_coeffs(coeffs, NUM_COEFFS - 1);
+    let prover = KZGProver::new(&params);
+    let domain = EvaluationDomain::new(NUM_COEFFS).unwrap();
+
+    c.bench_function(
+        format!("open_all_on_domain, degree {}", NUM_COEFFS - 1).as_str(),
+        |b| {
+            b.iter(|| {
+                black_box(&prover)
+                    .open_all_on_domain(black_box(&polynomial), black_box(&domain))
+                    .unwrap()
+            })
+        },
+    );
+}
+
 criterion_group!(
     name = create_witness;
     config = Criterion::default();
-    targets = bench_create_witness<16>, bench_create_witness<64>, bench_create_witness<128>, bench_create_witness<256>, bench_create_witness<512>, bench_create_witness<1024>, bench_create_witness<2048>, bench_create_witness<5096>
+    targets = bench_create_witness<16>, bench_create_witness<64>, bench_create_witness<128>, bench_create_witness<256>, bench_create_witness<512>, bench_create_witness<1024>, bench_create_witness<2048>, bench_create_witness<5096>, bench_open_all_on_domain<16>, bench_open_all_on_domain<128>, bench_open_all_on_domain<1024>
 );
 criterion_main!(create_witness);
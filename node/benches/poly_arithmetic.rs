@@ -1,5 +1,6 @@
 use ark_bn254::Fr as Scalar;
 use ark_ff::{UniformRand, Zero};
+use melon::kzg::domain::EvaluationDomain;
 use melon::kzg::polynomial::Polynomial;
 use rand::{rngs::SmallRng, Rng, SeedableRng};
 
@@ -10,7 +11,7 @@ fn random_polynomial(rng: &mut SmallRng, n: usize) -> Polynomial {
     for coeff in coeffs.iter_mut().take(n) {
         *coeff = rng.gen::<u64>().into();
     }
-    Polynomial::new(coeffs)
+    Polynomial::new(coeffs).unwrap()
 }
 
 fn poly_arithmetic<const NUM_COEFFS: usize>(c: &mut Criterion) {
@@ -38,7 +39,23 @@ fn poly_arithmetic<const NUM_COEFFS: usize>(c: &mut Criterion) {
 
     c.bench_function(
         format!("interpolation, degree {}", NUM_COEFFS - 1).as_str(),
-        |b| b.iter(|| Polynomial::lagrange_interpolation(xs.as_slice(), ys.as_slice())),
+        |b| b.iter(|| Polynomial::lagrange_interpolation(xs.as_slice(), ys.as_slice()).unwrap()),
+    );
+
+    c.bench_function(
+        format!("interpolation_fast, degree {}", NUM_COEFFS - 1).as_str(),
+        |b| b.iter(|| Polynomial::interpolate_fast(xs.as_slice(), ys.as_slice()).unwrap()),
+    );
+
+    c.bench_function(
+        format!("eval_many, degree {}", NUM_COEFFS - 1).as_str(),
+        |b| b.iter(|| black_box(&f).eval_many(xs.as_slice())),
+    );
+
+    let domain = EvaluationDomain::new(NUM_COEFFS).unwrap();
+    c.bench_function(
+        format!("interpolation_fft, domain size {}", domain.size()).as_str(),
+        |b| b.iter(|| Polynomial::from_evaluations_over_domain(ys.as_slice(), &domain).unwrap()),
     );
 }
 
@@ -0,0 +1,89 @@
+use ark_bn254::{Bn254, Fr as Scalar, G1Projective};
+use ark_ec::{PairingEngine, ProjectiveCurve};
+use ark_ff::{PrimeField, UniformRand};
+use melon::kzg::polynomial::Polynomial;
+use melon::kzg::{setup, EvalClaim, KZGCommitment, KZGParams, KZGProver, KZGVerifier, KZGWitness};
+use rand::{rngs::SmallRng, SeedableRng};
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+pub fn csprng_setup<const MAX_COEFFS: usize>() -> KZGParams {
+    let s: Scalar = rand::random::<u64>().into();
+    setup(s, MAX_COEFFS)
+}
+
+/// `verify_eval` as it was before preparing G2: two full pairings and a G2 scalar multiplication
+fn verify_eval_unprepared(
+    params: &KZGParams,
+    (x, y): (Scalar, Scalar),
+    commitment: &KZGCommitment,
+    witness: &KZGWitness,
+) -> bool {
+    let lhs = Bn254::pairing(*witness, params.hs[1] - params.hs[0].mul(x.into_repr()));
+    let rhs = Bn254::pairing(
+        G1Projective::from(*commitment) - params.gs[0].mul(y.into_repr()),
+        params.hs[0],
+    );
+
+    lhs == rhs
+}
+
+fn random_claim(prover: &KZGProver, rng: &mut SmallRng) -> EvalClaim {
+    let coeffs = (0..16).map(|_| Scalar::rand(rng)).collect();
+    let polynomial = Polynomial::new(coeffs).unwrap();
+    let x = Scalar::rand(rng);
+    let y = polynomial.eval(x);
+    let witness = prover.create_witness(&polynomial, (x, y)).unwrap();
+
+    (x, y, prover.commit(&polynomial).unwrap(), witness)
+}
+
+fn bench_verify_eval(c: &mut Criterion) {
+    let params = csprng_setup::<16>();
+    let prover = KZGProver::new(&params);
+    let verifier = KZGVerifier::new(&params).unwrap();
+    let mut rng = SmallRng::from_seed([42; 32]);
+    let (x, y, commitment, witness) = random_claim(&prover, &mut rng);
+
+    c.bench_function("verify_eval, unprepared", |b| {
+        b.iter(|| {
+            verify_eval_unprepared(
+                black_box(&params),
+                black_box((x, y)),
+                black_box(&commitment),
+                black_box(&witness),
+            )
+        })
+    });
+    c.bench_function("verify_eval", |b| {
+        b.iter(|| {
+            black_box(&verifier).verify_eval(
+                black_box((x, y)),
+                black_box(&commitment),
+                black_box(&witness),
+            )
+        })
+    });
+}
+
+fn bench_verify_eval_batch<const NUM_CLAIMS: usize>(c: &mut Criterion) {
+    let params = csprng_setup::<16>();
+    let prover = KZGProver::new(&params);
+    let verifier = KZGVerifier::new(&params).unwrap();
+    let mut rng = SmallRng::from_seed([42; 32]);
+    let claims = (0..NUM_CLAIMS)
+        .map(|_| random_claim(&prover, &mut rng))
+        .collect::<Vec<_>>();
+
+    c.bench_function(
+        format!("verify_eval_batch, {} claims", NUM_CLAIMS).as_str(),
+        |b| b.iter(|| black_box(&verifier).verify_eval_batch(black_box(&claims))),
+    );
+}
+
+criterion_group!(
+    name = verify_eval;
+    config = Criterion::default();
+    targets = bench_verify_eval, bench_verify_eval_batch<16>, bench_verify_eval_batch<128>
+);
+criterion_main!(verify_eval);
@@ -1,75 +1,299 @@
-use ark_bn254::{Fr as Scalar, G1Affine};
-use ark_ff::{One, PrimeField, Zero};
-use melon::kzg::polynomial::Polynomial;
-use melon::kzg::{setup, KZGCommitment, KZGParams, KZGProver, KZGVerifier};
-use rand::{rngs::SmallRng, Rng, SeedableRng};
-use serde::{Deserialize, Serialize};
-use std::fs::File;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use clap::builder::RangedU64ValueParser;
+use clap::{Parser, Subcommand, ValueEnum};
+use melon::kzg::blob::{Blob, Packing};
+use melon::kzg::bundle::ProofBundle;
+use melon::kzg::ptau::{load_ptau_blinding_powers_file, load_ptau_file};
+use melon::kzg::{abi, solidity};
+use melon::kzg::{setup, KZGParams, KZGProver};
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
 
-#[derive(Serialize, Deserialize)]
-struct JSONG1Affine {
-    x: String,
-    y: String,
-    i: String,
-    value: String,
+type CliResult<T> = Result<T, Box<dyn Error>>;
+
+/// KZG commitments to files over BN254. files are packed 31 bytes per field element and taken
+/// as the evaluations of a polynomial over a power-of-two roots-of-unity domain.
+#[derive(Parser)]
+#[command(name = "melon", version)]
+struct Cli {
+    /// parameters written by `melon setup`
+    #[arg(long, global = true, default_value = "params.bin")]
+    params: PathBuf,
+    /// the encoding of proof bundles: `json`, which the solidity verifier's tests read, or `hex`
+    /// for the binary one
+    #[arg(long, global = true, value_enum, default_value_t = Format::Json)]
+    format: Format,
+    #[command(subcommand)]
+    command: Command,
 }
 
-impl From<G1Affine> for JSONG1Affine {
-    fn from(point: G1Affine) -> Self {
-        JSONG1Affine {
-            x: format!("0x{}", point.x.into_repr()),
-            y: format!("0x{}", point.y.into_repr()),
-            i: "0x".to_string(),
-            value: "0x".to_string(),
-        }
-    }
+#[derive(Subcommand)]
+enum Command {
+    /// writes parameters for up to `num_coeffs` coefficients
+    Setup {
+        #[arg(long, default_value_t = 4096)]
+        num_coeffs: usize,
+        /// load the powers from a snarkjs powers-of-tau file
+        #[arg(long)]
+        ptau: Option<PathBuf>,
+        /// number of G2 powers to load from the ptau file, at least the 2 the verifier needs
+        #[arg(
+            long,
+            default_value_t = 2,
+            requires = "ptau",
+            value_parser = RangedU64ValueParser::<usize>::new().range(2..)
+        )]
+        num_g2: usize,
+        /// also load this many blinding powers for hiding commitments from the ptau file's
+        /// alphaTauG1 section
+        #[arg(long, requires = "ptau")]
+        blinding_powers: Option<usize>,
+        /// derive the powers from a known secret. insecure, for testing only; `1` matches the
+        /// contracts' `Constants.sol`
+        #[arg(long, conflicts_with = "ptau", required_unless_present = "ptau")]
+        secret: Option<u64>,
+    },
+    /// commits to a file
+    Commit {
+        file: PathBuf,
+        /// `-` for stdout
+        #[arg(short, long, default_value = "commitment.json")]
+        output: PathBuf,
+    },
+    /// opens a file's commitment at the index-th point of its domain, or with one batch witness
+    /// at several
+    Open {
+        file: PathBuf,
+        #[arg(long, required = true)]
+        index: Vec<usize>,
+        /// `-` for stdout
+        #[arg(short, long, default_value = "witness.json")]
+        output: PathBuf,
+    },
+    /// checks an opening against a commitment, exiting with an error if it doesn't verify
+    Verify {
+        #[arg(long, default_value = "commitment.json")]
+        commitment: PathBuf,
+        #[arg(long, default_value = "witness.json")]
+        witness: PathBuf,
+    },
+    /// prints the calldata for `Verifier.verify` checking an opening at one point, or for
+    /// `BatchVerifier.verifyBatch` at several
+    Calldata {
+        #[arg(long, default_value = "witness.json")]
+        witness: PathBuf,
+        /// `-` for stdout
+        #[arg(short, long, default_value = "-")]
+        output: PathBuf,
+    },
+    /// prints a summary of the parameters and checks that they are well formed
+    InspectParams,
+    /// writes the parameters as the solidity verifier's `Constants.sol`
+    ExportSolidity {
+        /// `-` for stdout
+        #[arg(short, long, default_value = "Constants.sol")]
+        output: PathBuf,
+        /// export only the first `num_g1` G1 powers, i.e. what the contract commits with
+        #[arg(long)]
+        num_g1: Option<usize>,
+        /// export only the first `num_g2` G2 powers; the verifier needs 2
+        #[arg(long, default_value_t = 2)]
+        num_g2: usize,
+    },
+    /// writes `BatchVerifier.sol`, checking openings at up to `num_points` points
+    ExportBatchVerifier {
+        /// `-` for stdout
+        #[arg(short, long, default_value = "BatchVerifier.sol")]
+        output: PathBuf,
+        #[arg(long, default_value_t = 4)]
+        num_points: usize,
+    },
 }
 
-pub fn csprng_setup<const MAX_COEFFS: usize>() -> KZGParams {
-    let s: Scalar = Scalar::one();
-    setup(s, MAX_COEFFS)
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Json,
+    Hex,
 }
 
-fn create_commit<const NUM_COEFFS: usize>() -> (Polynomial, KZGCommitment, KZGParams) {
-    let params = csprng_setup::<NUM_COEFFS>();
-    let mut rng = SmallRng::from_seed([42; 32]);
-    let mut coeffs = vec![Scalar::zero(); NUM_COEFFS];
-    for coeff in coeffs.iter_mut().take(NUM_COEFFS) {
-        *coeff = rng.gen::<u64>().into();
-    }
-    let polynomial = Polynomial::new_from_coeffs(coeffs, NUM_COEFFS - 1);
-    let prover = KZGProver::new(&params);
-    let commitment = prover.commit(&polynomial);
+fn main() -> CliResult<()> {
+    let cli = Cli::parse();
 
-    let commitment_json: JSONG1Affine = commitment.into();
+    match cli.command {
+        Command::Setup {
+            num_coeffs,
+            ptau,
+            num_g2,
+            blinding_powers,
+            secret,
+        } => {
+            let params = match (ptau, secret) {
+                (Some(ptau), _) => {
+                    let mut params = load_ptau_file(&ptau, num_coeffs, num_g2)?;
+                    if let Some(num_blinding) = blinding_powers {
+                        params.blinding_gs =
+                            Some(load_ptau_blinding_powers_file(&ptau, num_blinding)?);
+                    }
+                    params
+                }
+                (None, Some(secret)) => setup(secret.into(), num_coeffs),
+                (None, None) => unreachable!("clap requires one of --ptau and --secret"),
+            };
+            params.validate()?;
+            let mut writer = BufWriter::new(File::create(&cli.params)?);
+            CanonicalSerialize::serialize(&params, &mut writer)?;
+            writer.flush()?;
+        }
+        Command::Commit { file, output } => {
+            let params = read_params(&cli.params)?;
+            let blob = read_blob(&file)?;
+            let commitment = KZGProver::new(&params).commit_blob(&blob)?;
+            let bundle = ProofBundle::new_commitment(&params, commitment);
+            write_output(&output, &encode_bundle(&bundle, cli.format)?)?;
+        }
+        Command::Open {
+            file,
+            index,
+            output,
+        } => {
+            let params = read_params(&cli.params)?;
+            let blob = read_blob(&file)?;
+            let domain_size = blob.elements().len();
+            if let Some(index) = index.iter().find(|index| **index >= domain_size) {
+                return Err(format!(
+                    "index {} is outside the file's domain of size {}",
+                    index, domain_size
+                )
+                .into());
+            }
+
+            let points = index
+                .iter()
+                .map(|i| (blob.domain().element(*i), blob.elements()[*i]))
+                .collect::<Vec<_>>();
+            let prover = KZGProver::new(&params);
+            let polynomial = blob.to_polynomial();
+            let witness = match points.as_slice() {
+                [point] => prover.create_witness(&polynomial, *point)?,
+                points => prover.create_batch_witness(&polynomial, points)?,
+            };
+            let bundle = ProofBundle::new_batch_opening(
+                &params,
+                prover.commit_blob(&blob)?,
+                &points,
+                Some(witness),
+            );
+            write_output(&output, &encode_bundle(&bundle, cli.format)?)?;
+        }
+        Command::Verify {
+            commitment,
+            witness,
+        } => {
+            let params = read_params(&cli.params)?;
+            let commitment = read_bundle(&commitment)?;
+            let opening = read_bundle(&witness)?;
+
+            if opening.commitment != commitment.commitment {
+                return Err("the opening is for a different commitment".into());
+            }
+            if !opening.verify(&params)? {
+                return Err("opening does not verify".into());
+            }
+            println!("ok");
+        }
+        Command::Calldata { witness, output } => {
+            let opening = read_bundle(&witness)?;
+            let witness = opening.witness.ok_or("the bundle has no opening")?;
+            let calldata = match opening.openings().as_slice() {
+                [point] => abi::verify_calldata(&opening.commitment, &witness, *point),
+                points => abi::verify_batch_calldata(&opening.commitment, &witness, points),
+            };
+            write_output(&output, &format!("0x{}", hex::encode(calldata)))?;
+        }
+        Command::InspectParams => {
+            let params = read_params(&cli.params)?;
+            println!("G1 powers:      {}", params.gs.len());
+            println!("G2 powers:      {}", params.hs.len());
+            println!(
+                "lagrange basis: {}",
+                params
+                    .lagrange_domain()
+                    .map_or("none".to_string(), |domain| format!(
+                        "domain of size {}",
+                        domain.size()
+                    ))
+            );
+            println!(
+                "blinding powers: {}",
+                params.blinding_gs.as_ref().map_or(0, Vec::len)
+            );
+            params.validate()?;
+            println!("valid");
+        }
+        Command::ExportSolidity {
+            output,
+            num_g1,
+            num_g2,
+        } => {
+            let mut params = read_params(&cli.params)?;
+            params.gs.truncate(num_g1.unwrap_or(params.gs.len()));
+            params.hs.truncate(num_g2);
+            write_output(&output, &solidity::constants_sol(&params)?)?;
+        }
+        Command::ExportBatchVerifier { output, num_points } => {
+            let params = read_params(&cli.params)?;
+            write_output(&output, &solidity::batch_verifier_sol(&params, num_points)?)?;
+        }
+    }
 
-    serde_json::to_writer(&File::create("commitment.json").unwrap(), &commitment_json).unwrap();
+    Ok(())
+}
 
-    (polynomial, commitment, params)
+fn read_params(path: &Path) -> CliResult<KZGParams> {
+    let file = File::open(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(<KZGParams as CanonicalDeserialize>::deserialize(
+        BufReader::new(file),
+    )?)
 }
 
-fn create_witness<const NUM_COEFFS: usize>(
-    polynomial: Polynomial,
-    commitment: KZGCommitment,
-    params: KZGParams,
-) {
-    let prover = KZGProver::new(&params);
-    let mut rng = SmallRng::from_seed([42; 32]);
-    let x: Scalar = rng.gen::<u64>().into();
-    let y = polynomial.eval(x);
+fn read_blob(path: &Path) -> CliResult<Blob> {
+    Ok(Blob::new(&fs::read(path)?, Packing::Bytes31)?)
+}
 
-    let wit = prover.create_witness(&polynomial, (x, y)).unwrap();
-    let verifier = KZGVerifier::new(&params);
-    assert!(verifier.verify_eval((x, y), &commitment, &wit));
+/// a JSON or hex-encoded binary proof bundle
+fn read_bundle(path: &Path) -> CliResult<ProofBundle> {
+    let contents = fs::read_to_string(path)?;
+    if contents.trim_start().starts_with('{') {
+        return Ok(serde_json::from_str(&contents)?);
+    }
 
-    let mut wit_json: JSONG1Affine = wit.into();
-    wit_json.i = format!("0x{}", x.into_repr());
-    wit_json.value = format!("0x{}", y.into_repr());
+    let bytes = hex::decode(contents.trim().trim_start_matches("0x"))?;
+    let mut reader = bytes.as_slice();
+    let bundle = <ProofBundle as CanonicalDeserialize>::deserialize(&mut reader)?;
+    if !reader.is_empty() {
+        return Err("trailing bytes after proof bundle".into());
+    }
+    Ok(bundle)
+}
 
-    serde_json::to_writer(&File::create("witness.json").unwrap(), &wit_json).unwrap();
+fn encode_bundle(bundle: &ProofBundle, format: Format) -> CliResult<String> {
+    Ok(match format {
+        Format::Json => serde_json::to_string(bundle)?,
+        Format::Hex => {
+            let mut bytes = Vec::new();
+            CanonicalSerialize::serialize(bundle, &mut bytes)?;
+            format!("0x{}", hex::encode(bytes))
+        }
+    })
 }
 
-fn main() {
-    let (poly, commitment, params) = create_commit::<8>();
-    create_witness::<8>(poly, commitment, params);
+fn write_output(path: &Path, contents: &str) -> CliResult<()> {
+    if path == Path::new("-") {
+        println!("{}", contents.trim_end());
+    } else {
+        File::create(path)?.write_all(contents.as_bytes())?;
+    }
+    Ok(())
 }
@@ -0,0 +1,206 @@
+use super::transcript::TranscriptPoint;
+use super::{KZGCommitment, KZGWitness};
+use ark_bn254::{Fr as Scalar, G1Affine};
+use ark_ff::{BigInteger, PrimeField};
+use sha3::{Digest, Keccak256};
+
+/// signature of `Verifier.verify` in `contracts/src/KZGVerifier.sol`, with `Pairing.G1Point`
+/// as the tuple it is encoded as
+pub const VERIFY_SIGNATURE: &str = "verify((uint256,uint256),(uint256,uint256),uint256,uint256)";
+
+/// signature of `BatchVerifier.verifyBatch` in `contracts/src/BatchVerifier.sol`
+pub const VERIFY_BATCH_SIGNATURE: &str =
+    "verifyBatch((uint256,uint256),(uint256,uint256),uint256[],uint256[])";
+
+/// a solidity ABI value
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Token {
+    /// a uint256 as 32 big-endian bytes
+    Uint([u8; 32]),
+    /// a `Pairing.G1Point`, i.e. the static tuple (uint256 X, uint256 Y), with the identity as
+    /// (0, 0) like the precompiles
+    G1(G1Affine),
+    /// a dynamic array `T[]`
+    Array(Vec<Token>),
+}
+
+impl Token {
+    pub fn scalar<F: PrimeField>(scalar: F) -> Token {
+        let mut word = [0u8; 32];
+        let bytes = scalar.into_repr().to_bytes_be();
+        word[32 - bytes.len()..].copy_from_slice(&bytes);
+        Token::Uint(word)
+    }
+
+    fn is_dynamic(&self) -> bool {
+        matches!(self, Token::Array(_))
+    }
+
+    /// bytes taken in the head of an enclosing tuple
+    fn head_len(&self) -> usize {
+        match self {
+            Token::Uint(_) | Token::Array(_) => 32,
+            Token::G1(_) => 64,
+        }
+    }
+
+    /// the encoding of a static token, or the tail of a dynamic one
+    fn encode_into(&self, out: &mut Vec<u8>) {
+        match self {
+            Token::Uint(word) => out.extend_from_slice(word),
+            Token::G1(point) => out.extend(point.to_transcript_bytes()),
+            Token::Array(tokens) => {
+                out.extend_from_slice(&uint(tokens.len()));
+                out.extend(encode(tokens));
+            }
+        }
+    }
+}
+
+/// the ABI encoding of `tokens` as a tuple, i.e. of a function's arguments: static values in
+/// place, dynamic ones as an offset to their contents after the head
+pub fn encode(tokens: &[Token]) -> Vec<u8> {
+    let head_len: usize = tokens.iter().map(Token::head_len).sum();
+    let mut head = Vec::with_capacity(head_len);
+    let mut tail = Vec::new();
+
+    for token in tokens {
+        if token.is_dynamic() {
+            head.extend_from_slice(&uint(head_len + tail.len()));
+            token.encode_into(&mut tail);
+        } else {
+            token.encode_into(&mut head);
+        }
+    }
+
+    head.extend(tail);
+    head
+}
+
+/// the first four bytes of keccak256(signature), e.g. of "verify(uint256)"
+pub fn selector(signature: &str) -> [u8; 4] {
+    let hash = Keccak256::digest(signature.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// calldata calling `signature` with `tokens`
+pub fn encode_call(signature: &str, tokens: &[Token]) -> Vec<u8> {
+    let mut calldata = selector(signature).to_vec();
+    calldata.extend(encode(tokens));
+    calldata
+}
+
+/// calldata for `Verifier.verify(commitment, witness, x, y)`, checking the opening (x, y)
+pub fn verify_calldata(
+    commitment: &KZGCommitment,
+    witness: &KZGWitness,
+    (x, y): (Scalar, Scalar),
+) -> Vec<u8> {
+    encode_call(
+        VERIFY_SIGNATURE,
+        &[
+            Token::G1(*commitment),
+            Token::G1(*witness),
+            Token::scalar(x),
+            Token::scalar(y),
+        ],
+    )
+}
+
+/// calldata for `BatchVerifier.verifyBatch(commitment, witness, xs, ys)`, checking an opening
+/// from `KZGProver::create_batch_witness`
+pub fn verify_batch_calldata(
+    commitment: &KZGCommitment,
+    witness: &KZGWitness,
+    points: &[(Scalar, Scalar)],
+) -> Vec<u8> {
+    let (xs, ys): (Vec<_>, Vec<_>) = points
+        .iter()
+        .map(|(x, y)| (Token::scalar(*x), Token::scalar(*y)))
+        .unzip();
+    encode_call(
+        VERIFY_BATCH_SIGNATURE,
+        &[
+            Token::G1(*commitment),
+            Token::G1(*witness),
+            Token::Array(xs),
+            Token::Array(ys),
+        ],
+    )
+}
+
+fn uint(value: usize) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&(value as u64).to_be_bytes());
+    word
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ec::AffineCurve;
+    use ark_ff::Zero;
+
+    fn word(value: u64) -> Vec<u8> {
+        uint(value as usize).to_vec()
+    }
+
+    #[test]
+    fn test_selector() {
+        assert_eq!(
+            hex::encode(selector("transfer(address,uint256)")),
+            "a9059cbb"
+        );
+        assert_eq!(hex::encode(selector("baz(uint32,bool)")), "cdcd77c0");
+    }
+
+    #[test]
+    fn test_verify_calldata() {
+        let g = G1Affine::prime_subgroup_generator();
+        let calldata = verify_calldata(
+            &g,
+            &G1Affine::zero(),
+            (Scalar::from(3u64), Scalar::from(5u64)),
+        );
+
+        let mut expected = selector(VERIFY_SIGNATURE).to_vec();
+        for value in [1, 2, 0, 0, 3, 5] {
+            expected.extend(word(value));
+        }
+        assert_eq!(calldata, expected);
+        assert_eq!(calldata.len(), 4 + 6 * 32);
+
+        let points = [3u64, 4].map(|x| (Scalar::from(x), Scalar::from(x + 2)));
+        let calldata = verify_batch_calldata(&g, &G1Affine::zero(), &points);
+        let mut expected = selector(VERIFY_BATCH_SIGNATURE).to_vec();
+        for value in [1, 2, 0, 0, 0xc0, 0x120, 2, 3, 4, 2, 5, 6] {
+            expected.extend(word(value));
+        }
+        assert_eq!(calldata, expected);
+    }
+
+    #[test]
+    fn test_dynamic_arrays() {
+        // (uint256, uint256[], G1Point[]) = (7, [1, 2], [g])
+        let g = G1Affine::prime_subgroup_generator();
+        let encoded = encode(&[
+            Token::scalar(Scalar::from(7u64)),
+            Token::Array(vec![
+                Token::scalar(Scalar::from(1u64)),
+                Token::scalar(Scalar::from(2u64)),
+            ]),
+            Token::Array(vec![Token::G1(g)]),
+        ]);
+
+        let expected: Vec<u8> = [7, 0x60, 0xc0, 2, 1, 2, 1, 1, 2]
+            .iter()
+            .flat_map(|value| word(*value))
+            .collect();
+        assert_eq!(encoded, expected);
+
+        assert_eq!(
+            encode(&[Token::Array(vec![])]),
+            [word(0x20), word(0)].concat()
+        );
+    }
+}
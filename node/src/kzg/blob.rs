@@ -0,0 +1,304 @@
+use super::domain::EvaluationDomain;
+use super::polynomial::Polynomial;
+use super::{KZGCommitment, KZGError, KZGProver};
+use ark_bn254::Fr as Scalar;
+use ark_ec::PairingEngine;
+use ark_ff::{BigInteger, PrimeField};
+
+/// how bytes are packed into field elements, always big-endian
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Packing {
+    /// 31 bytes per element, so any input is canonical
+    Bytes31,
+    /// 32 bytes per element like EIP-4844 blobs. each chunk must already be less than the field
+    /// modulus, so this only suits data that is made of field elements to begin with.
+    Bytes32,
+}
+
+impl Packing {
+    pub fn bytes_per_element(&self) -> usize {
+        match self {
+            Packing::Bytes31 => 31,
+            Packing::Bytes32 => 32,
+        }
+    }
+
+    /// smallest domain holding `len` bytes
+    pub fn domain_for<F: PrimeField>(&self, len: usize) -> Option<EvaluationDomain<F>> {
+        EvaluationDomain::new(len.div_ceil(self.bytes_per_element()))
+    }
+}
+
+/// a byte payload as the evaluations of a polynomial over a roots-of-unity domain: element i is
+/// p(w^i). the last chunk is right-padded with zero bytes and the elements are padded with zeros
+/// to the domain size, so the byte length is kept alongside to decode exactly.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Blob<F: PrimeField = Scalar> {
+    packing: Packing,
+    len: usize,
+    elements: Vec<F>,
+}
+
+impl<F: PrimeField> Blob<F> {
+    /// packs `bytes`, failing for `Packing::Bytes32` if a chunk is not a canonical field element
+    pub fn new(bytes: &[u8], packing: Packing) -> Result<Blob<F>, KZGError> {
+        // 31-byte chunks must always be canonical, i.e. 2^248 < modulus
+        if F::size_in_bits() <= 248 {
+            return Err(KZGError::InvalidBlob(
+                "scalar field is too small for blobs".to_string(),
+            ));
+        }
+        let domain = domain_for::<F>(bytes.len(), packing)?;
+
+        let mut elements = bytes
+            .chunks(packing.bytes_per_element())
+            .enumerate()
+            .map(|(i, chunk)| {
+                let mut word = [0u8; 32];
+                let start = 32 - packing.bytes_per_element();
+                word[start..start + chunk.len()].copy_from_slice(chunk);
+                element_from_be_bytes(&word).ok_or_else(|| {
+                    KZGError::InvalidBlob(format!("element {} is not less than the modulus", i))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        elements.resize(domain.size(), F::zero());
+
+        Ok(Blob {
+            packing,
+            len: bytes.len(),
+            elements,
+        })
+    }
+
+    /// the blob of `len` bytes with the given elements, e.g. as received from a peer. rejects
+    /// elements that no byte string packs to, i.e. nonzero padding, or 31-byte elements of 2^248
+    /// or more.
+    pub fn from_elements(
+        elements: Vec<F>,
+        len: usize,
+        packing: Packing,
+    ) -> Result<Blob<F>, KZGError> {
+        let domain = domain_for::<F>(len, packing)?;
+        if elements.len() != domain.size() {
+            return Err(KZGError::InvalidBlob(format!(
+                "{} bytes take {} elements, not {}",
+                len,
+                domain.size(),
+                elements.len()
+            )));
+        }
+
+        let blob = Blob {
+            packing,
+            len,
+            elements,
+        };
+        let bytes = blob.to_bytes_unchecked();
+        let reencoded = Blob::<F>::new(&bytes[..len], packing)?;
+        if reencoded.elements != blob.elements {
+            return Err(KZGError::InvalidBlob(
+                "elements are not a canonical encoding".to_string(),
+            ));
+        }
+        Ok(blob)
+    }
+
+    /// the blob of `len` bytes whose elements are the evaluations of `polynomial`
+    pub fn from_polynomial(
+        polynomial: &Polynomial<F>,
+        len: usize,
+        packing: Packing,
+    ) -> Result<Blob<F>, KZGError> {
+        let domain = domain_for(len, packing)?;
+        Blob::from_elements(polynomial.evaluate_over_domain(&domain), len, packing)
+    }
+
+    /// length of the payload in bytes
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn packing(&self) -> Packing {
+        self.packing
+    }
+
+    /// evaluations over `domain()`, padding included
+    pub fn elements(&self) -> &[F] {
+        &self.elements
+    }
+
+    pub fn domain(&self) -> EvaluationDomain<F> {
+        EvaluationDomain::new(self.elements.len()).unwrap()
+    }
+
+    /// the polynomial in coefficient form
+    pub fn to_polynomial(&self) -> Polynomial<F> {
+        Polynomial::from_evaluations_over_domain(&self.elements, &self.domain())
+            .expect("the domain is sized to the elements")
+    }
+
+    /// the original payload
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.to_bytes_unchecked();
+        bytes.truncate(self.len);
+        bytes
+    }
+
+    /// every element's bytes, padding included
+    fn to_bytes_unchecked(&self) -> Vec<u8> {
+        let start = 32 - self.packing.bytes_per_element();
+        let mut bytes = Vec::with_capacity(self.elements.len() * self.packing.bytes_per_element());
+        for element in self.elements.iter() {
+            bytes.extend_from_slice(&element_to_be_bytes(element)[start..]);
+        }
+        bytes
+    }
+}
+
+impl<'params, E: PairingEngine> KZGProver<'params, E> {
+    /// commitment to `blob.to_polynomial()`, directly from the evaluations if the parameters have
+    /// a lagrange basis over the blob's domain
+    pub fn commit_blob(&self, blob: &Blob<E::Fr>) -> Result<KZGCommitment<E>, KZGError> {
+        if self.parameters().lagrange_domain() == Some(blob.domain()) {
+            return self.commit_evaluations(blob.elements());
+        }
+
+        let polynomial = blob.to_polynomial();
+        if polynomial.num_coeffs() > self.parameters().gs.len() {
+            return Err(KZGError::PolynomialDegreeTooLarge);
+        }
+        self.commit(&polynomial)
+    }
+}
+
+fn domain_for<F: PrimeField>(
+    len: usize,
+    packing: Packing,
+) -> Result<EvaluationDomain<F>, KZGError> {
+    packing
+        .domain_for(len)
+        .ok_or_else(|| KZGError::InvalidBlob(format!("{} bytes is too large", len)))
+}
+
+fn element_from_be_bytes<F: PrimeField>(bytes: &[u8; 32]) -> Option<F> {
+    let bits = bytes
+        .iter()
+        .flat_map(|b| (0..8).rev().map(move |i| (b >> i) & 1 == 1))
+        .collect::<Vec<bool>>();
+    F::from_repr(F::BigInt::from_bits_be(&bits))
+}
+
+/// the low 32 bytes of the element's big-endian representation, which is all of it for the
+/// fields blobs are used with
+fn element_to_be_bytes<F: PrimeField>(element: &F) -> [u8; 32] {
+    let repr = element.into_repr().to_bytes_be();
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(&repr[repr.len() - 32..]);
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kzg::{setup, KZGVerifier};
+    use ark_ff::{Field, FpParameters, One, Zero};
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+    use std::convert::TryInto;
+
+    const RNG_SEED: [u8; 32] = [69; 32];
+
+    #[test]
+    fn test_roundtrip() {
+        let mut rng = SmallRng::from_seed(RNG_SEED);
+
+        for len in [0, 1, 30, 31, 32, 33, 100, 31 * 8, 31 * 8 + 1] {
+            let bytes: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+            let blob: Blob = Blob::new(&bytes, Packing::Bytes31).unwrap();
+            assert_eq!(blob.len(), len);
+            assert!(blob.elements().len().is_power_of_two());
+            assert_eq!(blob.to_bytes(), bytes);
+
+            let decoded = Blob::from_polynomial(&blob.to_polynomial(), len, Packing::Bytes31);
+            assert_eq!(decoded.unwrap(), blob);
+        }
+
+        // any bytes with a zero high byte per chunk are canonical 32-byte elements
+        let mut bytes: Vec<u8> = (0..100).map(|_| rng.gen()).collect();
+        bytes.iter_mut().step_by(32).for_each(|b| *b = 0);
+        let blob: Blob = Blob::new(&bytes, Packing::Bytes32).unwrap();
+        assert_eq!(blob.elements().len(), 4);
+        assert_eq!(blob.to_bytes(), bytes);
+        assert_eq!(
+            Blob::from_elements(blob.elements().to_vec(), 100, Packing::Bytes32).unwrap(),
+            blob
+        );
+    }
+
+    #[test]
+    fn test_rejects_non_canonical() {
+        let modulus: [u8; 32] = <Scalar as PrimeField>::Params::MODULUS
+            .to_bytes_be()
+            .try_into()
+            .unwrap();
+        assert!(matches!(
+            Blob::<Scalar>::new(&modulus, Packing::Bytes32),
+            Err(KZGError::InvalidBlob(_))
+        ));
+        assert!(Blob::<Scalar>::new(&[0xff; 32], Packing::Bytes32).is_err());
+        assert!(Blob::<Scalar>::new(&modulus, Packing::Bytes31).is_ok());
+
+        let blob: Blob = Blob::new(&[1, 2, 3], Packing::Bytes31).unwrap();
+
+        // 2^248 doesn't fit in 31 bytes
+        let mut elements = blob.elements().to_vec();
+        elements[0] = Scalar::from(2u64).pow([248]);
+        assert!(Blob::from_elements(elements, 3, Packing::Bytes31).is_err());
+
+        // nonzero bytes past the end of the payload
+        let mut elements = blob.elements().to_vec();
+        elements[0] += Scalar::one();
+        assert!(Blob::from_elements(elements, 3, Packing::Bytes31).is_err());
+
+        // 70 bytes take 3 elements, padded to 4
+        let blob: Blob = Blob::new(&[7; 70], Packing::Bytes31).unwrap();
+        let mut elements = blob.elements().to_vec();
+        assert!(elements[3].is_zero());
+        elements[3] = Scalar::one();
+        assert!(Blob::from_elements(elements, 70, Packing::Bytes31).is_err());
+
+        assert!(Blob::from_elements(vec![Scalar::zero(); 3], 70, Packing::Bytes31).is_err());
+        assert!(Blob::from_elements(vec![Scalar::zero(); 4], 31, Packing::Bytes31).is_err());
+    }
+
+    #[test]
+    fn test_commit_blob() {
+        let mut rng = SmallRng::from_seed(RNG_SEED);
+        let bytes: Vec<u8> = (0..200).map(|_| rng.gen()).collect();
+        let blob: Blob = Blob::new(&bytes, Packing::Bytes31).unwrap();
+        let polynomial = blob.to_polynomial();
+
+        let params = setup(rng.gen::<u64>().into(), 8);
+        let commitment = KZGProver::new(&params).commit_blob(&blob).unwrap();
+        assert!(KZGVerifier::new(&params)
+            .unwrap()
+            .verify_poly(&commitment, &polynomial)
+            .unwrap());
+
+        let params = params.with_lagrange_basis(&blob.domain()).unwrap();
+        assert_eq!(
+            KZGProver::new(&params).commit_blob(&blob).unwrap(),
+            commitment
+        );
+
+        let small_params = setup(rng.gen::<u64>().into(), 4);
+        assert!(matches!(
+            KZGProver::new(&small_params).commit_blob(&blob),
+            Err(KZGError::PolynomialDegreeTooLarge)
+        ));
+    }
+}
@@ -0,0 +1,551 @@
+use super::{KZGCommitment, KZGError, KZGParams, KZGVerifier, KZGWitness};
+use ark_bls12_381::Bls12_381;
+use ark_bn254::Bn254;
+use ark_ec::PairingEngine;
+use ark_ec::{models::SWModelParameters, short_weierstrass_jacobian::GroupAffine};
+use ark_ff::{BigInteger, PrimeField, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write};
+use serde::{de, Deserializer, Serializer};
+use std::convert::TryFrom;
+
+/// the version written into, and required of, both encodings of a `ProofBundle`
+pub const BUNDLE_VERSION: u8 = 1;
+
+/// the curve a bundle is over: a byte in the binary encoding, a name in JSON
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CurveId {
+    Bn254 = 1,
+    Bls12_381 = 2,
+}
+
+impl CurveId {
+    pub fn name(self) -> &'static str {
+        match self {
+            CurveId::Bn254 => "bn254",
+            CurveId::Bls12_381 => "bls12-381",
+        }
+    }
+}
+
+/// a pairing engine that bundles can be encoded for
+pub trait BundleCurve: PairingEngine {
+    const CURVE_ID: CurveId;
+
+    /// (x, y) of a G1 point, or `None` for the identity
+    fn g1_coordinates(point: &Self::G1Affine) -> Option<(Self::Fq, Self::Fq)>;
+
+    /// the G1 point (x, y), if it is on the curve and in the prime-order subgroup
+    fn g1_from_coordinates(x: Self::Fq, y: Self::Fq) -> Option<Self::G1Affine>;
+}
+
+fn coordinates<P: SWModelParameters>(
+    point: &GroupAffine<P>,
+) -> Option<(P::BaseField, P::BaseField)> {
+    (!point.infinity).then_some((point.x, point.y))
+}
+
+fn from_coordinates<P: SWModelParameters>(
+    x: P::BaseField,
+    y: P::BaseField,
+) -> Option<GroupAffine<P>> {
+    let point = GroupAffine::new(x, y, false);
+    (point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve()).then_some(point)
+}
+
+macro_rules! impl_bundle_curve {
+    ($engine:ty, $id:expr) => {
+        impl BundleCurve for $engine {
+            const CURVE_ID: CurveId = $id;
+
+            fn g1_coordinates(point: &Self::G1Affine) -> Option<(Self::Fq, Self::Fq)> {
+                coordinates(point)
+            }
+
+            fn g1_from_coordinates(x: Self::Fq, y: Self::Fq) -> Option<Self::G1Affine> {
+                from_coordinates(x, y)
+            }
+        }
+    };
+}
+
+impl_bundle_curve!(Bn254, CurveId::Bn254);
+impl_bundle_curve!(Bls12_381, CurveId::Bls12_381);
+
+/// a commitment and, optionally, a witness that the committed polynomial takes
+/// `evaluations[i]` at `points[i]`, tied to the SRS it was made with by `KZGParams::srs_hash`.
+/// without points it is just the commitment; with one point the witness is from
+/// `KZGProver::create_witness`, with several from `create_batch_witness`.
+///
+/// the binary encoding is the canonical (compressed) one:
+///     version: u8 | curve: u8 | srs_hash: [u8; 32] | commitment | points | evaluations | witness
+/// with vectors u64-length-prefixed and the witness as an option. the JSON encoding is
+///     {"version": 1, "curve": "bn254", "srs_hash": "0x..", "commitment": {"x": "0x..", "y": ".."},
+///      "points": ["0x.."], "evaluations": ["0x.."], "witness": {"x": "0x..", "y": "0x.."}}
+/// with every value 0x-prefixed, fixed-width big-endian hex, the identity as x = y = 0 and
+/// `witness` null when there are no points. both parsers reject unknown versions and curves,
+/// points that are off the curve or outside the prime-order subgroup, and unreduced field
+/// elements.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProofBundle<E: PairingEngine = Bn254> {
+    pub srs_hash: [u8; 32],
+    pub commitment: KZGCommitment<E>,
+    pub points: Vec<E::Fr>,
+    pub evaluations: Vec<E::Fr>,
+    pub witness: Option<KZGWitness<E>>,
+}
+
+impl<E: PairingEngine> ProofBundle<E> {
+    pub fn new_commitment(params: &KZGParams<E>, commitment: KZGCommitment<E>) -> Self {
+        Self::new_batch_opening(params, commitment, &[], None)
+    }
+
+    pub fn new_opening(
+        params: &KZGParams<E>,
+        commitment: KZGCommitment<E>,
+        point: (E::Fr, E::Fr),
+        witness: KZGWitness<E>,
+    ) -> Self {
+        Self::new_batch_opening(params, commitment, &[point], Some(witness))
+    }
+
+    pub fn new_batch_opening(
+        params: &KZGParams<E>,
+        commitment: KZGCommitment<E>,
+        points: &[(E::Fr, E::Fr)],
+        witness: Option<KZGWitness<E>>,
+    ) -> Self {
+        let (points, evaluations) = points.iter().copied().unzip();
+        ProofBundle {
+            srs_hash: params.srs_hash(),
+            commitment,
+            points,
+            evaluations,
+            witness,
+        }
+    }
+
+    /// the (x, y) pairs the witness opens
+    pub fn openings(&self) -> Vec<(E::Fr, E::Fr)> {
+        self.points
+            .iter()
+            .copied()
+            .zip(self.evaluations.iter().copied())
+            .collect()
+    }
+
+    /// checks the opening against `params`. fails if the bundle has no opening or was made with
+    /// a different SRS.
+    pub fn verify(&self, params: &KZGParams<E>) -> Result<bool, KZGError> {
+        self.check()?;
+        if self.srs_hash != params.srs_hash() {
+            return Err(KZGError::InvalidBundle(
+                "made with a different SRS".to_string(),
+            ));
+        }
+
+        let verifier = KZGVerifier::new(params)?;
+        match (self.witness, self.openings().as_slice()) {
+            (None, _) => Err(KZGError::InvalidBundle("no opening to verify".to_string())),
+            (Some(witness), [point]) => {
+                Ok(verifier.verify_eval(*point, &self.commitment, &witness))
+            }
+            (Some(witness), points) => {
+                Ok(verifier.verify_batch_eval(points, &self.commitment, &witness))
+            }
+        }
+    }
+
+    /// a witness comes with at least one point, and every point with an evaluation
+    fn check(&self) -> Result<(), KZGError> {
+        if self.points.len() != self.evaluations.len() {
+            return Err(KZGError::PointMismatch(
+                self.points.len(),
+                self.evaluations.len(),
+            ));
+        }
+        if self.points.is_empty() == self.witness.is_some() {
+            return Err(KZGError::InvalidBundle(
+                "a witness needs points, and points need a witness".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl<E: BundleCurve> CanonicalSerialize for ProofBundle<E> {
+    fn serialize<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+        BUNDLE_VERSION.serialize(&mut writer)?;
+        (E::CURVE_ID as u8).serialize(&mut writer)?;
+        writer.write_all(&self.srs_hash)?;
+        self.commitment.serialize(&mut writer)?;
+        self.points.serialize(&mut writer)?;
+        self.evaluations.serialize(&mut writer)?;
+        self.witness.serialize(&mut writer)
+    }
+
+    fn serialized_size(&self) -> usize {
+        2 + self.srs_hash.len()
+            + self.commitment.serialized_size()
+            + self.points.serialized_size()
+            + self.evaluations.serialized_size()
+            + self.witness.serialized_size()
+    }
+}
+
+impl<E: BundleCurve> CanonicalDeserialize for ProofBundle<E> {
+    fn deserialize<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+        if u8::deserialize(&mut reader)? != BUNDLE_VERSION
+            || u8::deserialize(&mut reader)? != E::CURVE_ID as u8
+        {
+            return Err(SerializationError::InvalidData);
+        }
+
+        let mut srs_hash = [0u8; 32];
+        reader.read_exact(&mut srs_hash)?;
+        let bundle = ProofBundle {
+            srs_hash,
+            commitment: CanonicalDeserialize::deserialize(&mut reader)?,
+            points: CanonicalDeserialize::deserialize(&mut reader)?,
+            evaluations: CanonicalDeserialize::deserialize(&mut reader)?,
+            witness: CanonicalDeserialize::deserialize(&mut reader)?,
+        };
+        bundle
+            .check()
+            .map_err(|_| SerializationError::InvalidData)?;
+        Ok(bundle)
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonBundle {
+    version: u8,
+    curve: String,
+    srs_hash: String,
+    commitment: JsonPoint,
+    points: Vec<String>,
+    evaluations: Vec<String>,
+    witness: Option<JsonPoint>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonPoint {
+    x: String,
+    y: String,
+}
+
+impl JsonPoint {
+    fn from_point<E: BundleCurve>(point: &E::G1Affine) -> Self {
+        let (x, y) = E::g1_coordinates(point).unwrap_or_else(|| (E::Fq::zero(), E::Fq::zero()));
+        JsonPoint {
+            x: field_to_hex(&x),
+            y: field_to_hex(&y),
+        }
+    }
+
+    fn to_point<E: BundleCurve>(&self) -> Result<E::G1Affine, KZGError> {
+        let (x, y): (E::Fq, E::Fq) = (field_from_hex(&self.x)?, field_from_hex(&self.y)?);
+        if x.is_zero() && y.is_zero() {
+            return Ok(E::G1Affine::zero());
+        }
+
+        E::g1_from_coordinates(x, y).ok_or_else(|| {
+            KZGError::InvalidBundle(format!(
+                "({}, {}) is not a point of the prime-order subgroup",
+                self.x, self.y
+            ))
+        })
+    }
+}
+
+fn field_to_hex<F: PrimeField>(value: &F) -> String {
+    format!("0x{}", hex::encode(value.into_repr().to_bytes_be()))
+}
+
+/// the inverse of `field_to_hex`, which only accepts reduced elements written at full width
+fn field_from_hex<F: PrimeField>(s: &str) -> Result<F, KZGError> {
+    let mut bytes = parse_hex(s, F::zero().into_repr().to_bytes_be().len())?;
+    bytes.reverse();
+    let repr = F::BigInt::deserialize(bytes.as_slice())
+        .map_err(|e| KZGError::InvalidBundle(format!("{}: {}", s, e)))?;
+    F::from_repr(repr).ok_or_else(|| KZGError::InvalidBundle(format!("{} is not reduced", s)))
+}
+
+fn parse_hex(s: &str, len: usize) -> Result<Vec<u8>, KZGError> {
+    let digits = s
+        .strip_prefix("0x")
+        .ok_or_else(|| KZGError::InvalidBundle(format!("{} is missing the 0x prefix", s)))?;
+    let bytes =
+        hex::decode(digits).map_err(|e| KZGError::InvalidBundle(format!("{}: {}", s, e)))?;
+    if bytes.len() != len {
+        return Err(KZGError::InvalidBundle(format!(
+            "{} is {} bytes, expected {}",
+            s,
+            bytes.len(),
+            len
+        )));
+    }
+    Ok(bytes)
+}
+
+impl<E: BundleCurve> From<&ProofBundle<E>> for JsonBundle {
+    fn from(bundle: &ProofBundle<E>) -> Self {
+        JsonBundle {
+            version: BUNDLE_VERSION,
+            curve: E::CURVE_ID.name().to_string(),
+            srs_hash: format!("0x{}", hex::encode(bundle.srs_hash)),
+            commitment: JsonPoint::from_point::<E>(&bundle.commitment),
+            points: bundle.points.iter().map(field_to_hex).collect(),
+            evaluations: bundle.evaluations.iter().map(field_to_hex).collect(),
+            witness: bundle.witness.as_ref().map(JsonPoint::from_point::<E>),
+        }
+    }
+}
+
+impl<E: BundleCurve> TryFrom<JsonBundle> for ProofBundle<E> {
+    type Error = KZGError;
+
+    fn try_from(json: JsonBundle) -> Result<Self, KZGError> {
+        if json.version != BUNDLE_VERSION {
+            return Err(KZGError::InvalidBundle(format!(
+                "unsupported version {}",
+                json.version
+            )));
+        }
+        if json.curve != E::CURVE_ID.name() {
+            return Err(KZGError::InvalidBundle(format!(
+                "expected curve {}, got {}",
+                E::CURVE_ID.name(),
+                json.curve
+            )));
+        }
+
+        let mut srs_hash = [0u8; 32];
+        srs_hash.copy_from_slice(&parse_hex(&json.srs_hash, 32)?);
+        let parse_scalars = |values: &[String]| {
+            values
+                .iter()
+                .map(|value| field_from_hex(value))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        let bundle = ProofBundle {
+            srs_hash,
+            commitment: json.commitment.to_point::<E>()?,
+            points: parse_scalars(&json.points)?,
+            evaluations: parse_scalars(&json.evaluations)?,
+            witness: json
+                .witness
+                .map(|witness| witness.to_point::<E>())
+                .transpose()?,
+        };
+        bundle.check()?;
+        Ok(bundle)
+    }
+}
+
+impl<E: BundleCurve> serde::Serialize for ProofBundle<E> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(&JsonBundle::from(self), serializer)
+    }
+}
+
+impl<'de, E: BundleCurve> serde::Deserialize<'de> for ProofBundle<E> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let json: JsonBundle = serde::Deserialize::deserialize(deserializer)?;
+        ProofBundle::try_from(json).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kzg::domain::EvaluationDomain;
+    use crate::kzg::polynomial::Polynomial;
+    use crate::kzg::{setup, KZGProver};
+    use ark_bn254::{Fq, Fr as Scalar};
+    use ark_ff::{FpParameters, One, UniformRand};
+    use rand::{rngs::SmallRng, SeedableRng};
+
+    const RNG_SEED: [u8; 32] = [69; 32];
+
+    fn test_bundles(params: &KZGParams) -> Vec<ProofBundle> {
+        let mut rng = SmallRng::from_seed(RNG_SEED);
+        let coeffs = (0..8).map(|_| Scalar::rand(&mut rng)).collect();
+        let polynomial = Polynomial::new(coeffs).unwrap();
+        let prover = KZGProver::new(params);
+        let commitment = prover.commit(&polynomial).unwrap();
+
+        let x = Scalar::rand(&mut rng);
+        let point = (x, polynomial.eval(x));
+        let points = (1..=3u64)
+            .map(|x| (x.into(), polynomial.eval(x.into())))
+            .collect::<Vec<_>>();
+
+        vec![
+            ProofBundle::new_commitment(params, commitment),
+            ProofBundle::new_opening(
+                params,
+                commitment,
+                point,
+                prover.create_witness(&polynomial, point).unwrap(),
+            ),
+            ProofBundle::new_batch_opening(
+                params,
+                commitment,
+                &points,
+                Some(prover.create_batch_witness(&polynomial, &points).unwrap()),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_roundtrips_and_verifies() {
+        let params = setup(12345u64.into(), 8);
+        let bundles = test_bundles(&params);
+
+        for bundle in &bundles {
+            let json = serde_json::to_string(bundle).unwrap();
+            assert_eq!(&serde_json::from_str::<ProofBundle>(&json).unwrap(), bundle);
+
+            let mut bytes = Vec::new();
+            bundle.serialize(&mut bytes).unwrap();
+            assert_eq!(bytes.len(), bundle.serialized_size());
+            assert_eq!(&ProofBundle::deserialize(bytes.as_slice()).unwrap(), bundle);
+        }
+
+        assert!(matches!(
+            bundles[0].verify(&params),
+            Err(KZGError::InvalidBundle(_))
+        ));
+        assert!(bundles[1].verify(&params).unwrap());
+        assert!(bundles[2].verify(&params).unwrap());
+
+        let mut wrong_value = bundles[2].clone();
+        wrong_value.evaluations[1] += Scalar::one();
+        assert!(!wrong_value.verify(&params).unwrap());
+
+        let other_params = setup(54321u64.into(), 8);
+        assert!(matches!(
+            bundles[1].verify(&other_params),
+            Err(KZGError::InvalidBundle(_))
+        ));
+
+        // the blinding powers are part of the SRS, the lagrange basis isn't
+        let blinding = params.clone().with_insecure_blinding_powers(678u64.into());
+        assert_ne!(blinding.srs_hash(), params.srs_hash());
+        assert_ne!(
+            blinding.srs_hash(),
+            params
+                .clone()
+                .with_insecure_blinding_powers(876u64.into())
+                .srs_hash()
+        );
+        assert!(matches!(
+            bundles[1].verify(&blinding),
+            Err(KZGError::InvalidBundle(_))
+        ));
+        let lagrange = params
+            .clone()
+            .with_lagrange_basis(&EvaluationDomain::new(8).unwrap())
+            .unwrap();
+        assert!(bundles[1].verify(&lagrange).unwrap());
+    }
+
+    #[test]
+    fn test_json_layout() {
+        let params = setup(12345u64.into(), 8);
+        let bundles = test_bundles(&params);
+        let json = serde_json::to_value(&bundles[1]).unwrap();
+
+        assert_eq!(json["version"], 1);
+        assert_eq!(json["curve"], "bn254");
+        assert_eq!(
+            json["srs_hash"],
+            format!("0x{}", hex::encode(params.srs_hash()))
+        );
+        assert_eq!(
+            json["commitment"]["x"],
+            field_to_hex(&bundles[1].commitment.x)
+        );
+        assert_eq!(json["points"][0].as_str().unwrap().len(), 2 + 64);
+        assert!(serde_json::to_value(&bundles[0]).unwrap()["witness"].is_null());
+
+        // the identity is (0, 0), and BLS12-381 coordinates are 48 bytes wide
+        let identity = ProofBundle::<Bls12_381>::new_commitment(
+            &KZGParams::from_secret(12345u64.into(), 2),
+            ark_bls12_381::G1Affine::zero(),
+        );
+        let json = serde_json::to_value(&identity).unwrap();
+        assert_eq!(json["curve"], "bls12-381");
+        assert_eq!(json["commitment"]["x"], format!("0x{}", "00".repeat(48)));
+        assert_eq!(
+            serde_json::from_value::<ProofBundle<Bls12_381>>(json.clone()).unwrap(),
+            identity
+        );
+        assert!(serde_json::from_value::<ProofBundle>(json).is_err());
+    }
+
+    #[test]
+    fn test_rejects_invalid_bundles() {
+        let params = setup(12345u64.into(), 8);
+        let bundle = &test_bundles(&params)[1];
+        let json = serde_json::to_value(bundle).unwrap();
+        let rejects = |edit: &dyn Fn(&mut serde_json::Value)| {
+            let mut json = json.clone();
+            edit(&mut json);
+            serde_json::from_value::<ProofBundle>(json).is_err()
+        };
+
+        // (1, 2) is the generator, so (1, 3) is off the curve
+        assert!(rejects(&|json| {
+            json["witness"] = serde_json::json!({
+                "x": field_to_hex(&Fq::one()),
+                "y": field_to_hex(&Fq::from(3u64)),
+            })
+        }));
+        let modulus = format!(
+            "0x{}",
+            hex::encode(<Scalar as PrimeField>::Params::MODULUS.to_bytes_be())
+        );
+        assert!(rejects(&|json| json["points"][0] = modulus.clone().into()));
+        assert!(rejects(&|json| json["points"][0] = "0x01".into()));
+        let unprefixed = json["evaluations"][0].as_str().unwrap()[2..].to_string();
+        assert!(rejects(
+            &|json| json["evaluations"][0] = unprefixed.clone().into()
+        ));
+        assert!(rejects(&|json| json["version"] = 2.into()));
+        assert!(rejects(&|json| json["extra"] = 0.into()));
+        assert!(rejects(&|json| json["evaluations"] = serde_json::json!([])));
+        assert!(rejects(&|json| json["witness"] = serde_json::Value::Null));
+        assert!(!rejects(&|_| ()));
+
+        let mut bytes = Vec::new();
+        bundle.serialize(&mut bytes).unwrap();
+        for (i, value) in [(0, BUNDLE_VERSION + 1), (1, CurveId::Bls12_381 as u8)] {
+            let mut bytes = bytes.clone();
+            bytes[i] = value;
+            assert!(ProofBundle::<Bn254>::deserialize(bytes.as_slice()).is_err());
+        }
+        assert!(ProofBundle::<Bn254>::deserialize(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_contract_test_files() {
+        // written by `melon setup --secret 1 --num-coeffs 129`, then `melon commit` and
+        // `melon open`, for the solidity verifier's tests
+        let params = setup(Scalar::one(), 129);
+        let commitment: ProofBundle = serde_json::from_str(include_str!(
+            "../../../contracts/test/files/commitment.json"
+        ))
+        .unwrap();
+        let opening: ProofBundle =
+            serde_json::from_str(include_str!("../../../contracts/test/files/witness.json"))
+                .unwrap();
+
+        assert_eq!(commitment.commitment, opening.commitment);
+        assert_eq!(opening.points.len(), 1);
+        assert!(opening.verify(&params).unwrap());
+    }
+}
@@ -0,0 +1,357 @@
+use super::KZGError;
+use ark_bn254::Fr as Scalar;
+use ark_ff::{batch_inversion, FftField, FftParameters, Zero};
+use std::ops::{Add, MulAssign, Sub};
+
+/// anything that can be transformed by the NTT over `F`: scalars, but also group elements
+pub trait DomainCoeff<F: FftField>:
+    Copy + Send + Sync + Zero + Add<Output = Self> + Sub<Output = Self> + MulAssign<F>
+{
+}
+
+impl<F: FftField, T> DomainCoeff<F> for T where
+    T: Copy + Send + Sync + Zero + Add<Output = T> + Sub<Output = T> + MulAssign<F>
+{
+}
+
+/// multiplicative subgroup of `F` (BN254's scalar field by default) of size 2^k, i.e. the roots of unity 1, w, w^2, ..., w^(n-1)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EvaluationDomain<F: FftField = Scalar> {
+    size: usize,
+    log_size: u32,
+    generator: F,
+    generator_inv: F,
+    size_inv: F,
+    offset: F,
+    offset_inv: F,
+}
+
+impl<F: FftField> EvaluationDomain<F> {
+    /// smallest domain with at least `num_coeffs` points, or `None` if the field has no such subgroup
+    pub fn new(num_coeffs: usize) -> Option<Self> {
+        let size = num_coeffs.max(1).checked_next_power_of_two()?;
+        let log_size = size.trailing_zeros();
+        if log_size > <F as FftField>::FftParams::TWO_ADICITY {
+            return None;
+        }
+
+        let generator = F::get_root_of_unity(size)?;
+        let offset = F::multiplicative_generator();
+
+        Some(EvaluationDomain {
+            size,
+            log_size,
+            generator,
+            generator_inv: generator.inverse()?,
+            size_inv: F::from(size as u64).inverse()?,
+            offset,
+            offset_inv: offset.inverse()?,
+        })
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn log_size(&self) -> u32 {
+        self.log_size
+    }
+
+    /// primitive `size`-th root of unity w
+    pub fn generator(&self) -> F {
+        self.generator
+    }
+
+    /// shift used by the coset transforms, i.e. the coset is offset * <w>
+    pub fn coset_offset(&self) -> F {
+        self.offset
+    }
+
+    /// w^i
+    pub fn element(&self, i: usize) -> F {
+        self.generator.pow([(i % self.size) as u64])
+    }
+
+    /// 1, w, w^2, ..., w^(n-1)
+    pub fn elements(&self) -> Vec<F> {
+        powers(F::one(), self.generator, self.size)
+    }
+
+    /// evaluates X^n - 1, the polynomial vanishing on every point of the domain
+    pub fn evaluate_vanishing_polynomial(&self, x: F) -> F {
+        x.pow([self.size as u64]) - F::one()
+    }
+
+    /// evaluates the polynomial taking the values `evals` at 1, w, ..., w^(n-1) (missing values are
+    /// zero) at `z`, using the barycentric formula
+    ///     p(z) = (z^n - 1) / n * sum evals[i] * w^i / (z - w^i)
+    /// with O(n) field operations and a single inversion. fails if there are more values than
+    /// domain points.
+    pub fn evaluate_from_evaluations(&self, evals: &[F], z: F) -> Result<F, KZGError> {
+        self.check_fits(evals.len())?;
+
+        let roots = powers(F::one(), self.generator, evals.len());
+        let vanishing = self.evaluate_vanishing_polynomial(z);
+        if vanishing.is_zero() {
+            // z is a domain point, so the formula divides by zero but the answer is known
+            return Ok(roots
+                .iter()
+                .position(|w| *w == z)
+                .map_or(F::zero(), |i| evals[i]));
+        }
+
+        let mut denominators: Vec<F> = roots.iter().map(|w| z - w).collect();
+        batch_inversion(&mut denominators);
+
+        let sum = evals
+            .iter()
+            .zip(roots.iter())
+            .zip(denominators.iter())
+            .map(|((p, w), d)| *p * w * d)
+            .sum::<F>();
+        Ok(sum * vanishing * self.size_inv)
+    }
+
+    /// coefficients -> evaluations at 1, w, ..., w^(n-1). `values` is zero-padded to the domain
+    /// size, and the transform fails if there are more values than domain points.
+    pub fn fft_in_place<T: DomainCoeff<F>>(&self, values: &mut Vec<T>) -> Result<(), KZGError> {
+        self.check_fits(values.len())?;
+        values.resize(self.size, T::zero());
+        radix2_fft(values, self.generator, self.log_size);
+        Ok(())
+    }
+
+    /// evaluations at 1, w, ..., w^(n-1) -> coefficients
+    pub fn ifft_in_place<T: DomainCoeff<F>>(&self, values: &mut Vec<T>) -> Result<(), KZGError> {
+        self.check_fits(values.len())?;
+        values.resize(self.size, T::zero());
+        radix2_fft(values, self.generator_inv, self.log_size);
+        values.iter_mut().for_each(|v| *v *= self.size_inv);
+        Ok(())
+    }
+
+    /// coefficients -> evaluations at offset * w^i
+    pub fn coset_fft_in_place<T: DomainCoeff<F>>(
+        &self,
+        values: &mut Vec<T>,
+    ) -> Result<(), KZGError> {
+        self.check_fits(values.len())?;
+        distribute_powers(values, self.offset);
+        self.fft_in_place(values)
+    }
+
+    /// evaluations at offset * w^i -> coefficients
+    pub fn coset_ifft_in_place<T: DomainCoeff<F>>(
+        &self,
+        values: &mut Vec<T>,
+    ) -> Result<(), KZGError> {
+        self.ifft_in_place(values)?;
+        distribute_powers(values, self.offset_inv);
+        Ok(())
+    }
+
+    pub fn fft(&self, coeffs: &[F]) -> Result<Vec<F>, KZGError> {
+        let mut values = coeffs.to_vec();
+        self.fft_in_place(&mut values)?;
+        Ok(values)
+    }
+
+    pub fn ifft(&self, evals: &[F]) -> Result<Vec<F>, KZGError> {
+        let mut values = evals.to_vec();
+        self.ifft_in_place(&mut values)?;
+        Ok(values)
+    }
+
+    pub fn coset_fft(&self, coeffs: &[F]) -> Result<Vec<F>, KZGError> {
+        let mut values = coeffs.to_vec();
+        self.coset_fft_in_place(&mut values)?;
+        Ok(values)
+    }
+
+    pub fn coset_ifft(&self, evals: &[F]) -> Result<Vec<F>, KZGError> {
+        let mut values = evals.to_vec();
+        self.coset_ifft_in_place(&mut values)?;
+        Ok(values)
+    }
+
+    fn check_fits(&self, len: usize) -> Result<(), KZGError> {
+        if len > self.size {
+            return Err(KZGError::DomainTooSmall(len, self.size));
+        }
+        Ok(())
+    }
+}
+
+fn powers<F: FftField>(start: F, base: F, n: usize) -> Vec<F> {
+    let mut res = Vec::with_capacity(n);
+    let mut curr = start;
+    for _ in 0..n {
+        res.push(curr);
+        curr *= base;
+    }
+    res
+}
+
+/// values[i] *= g^i
+fn distribute_powers<F: FftField, T: DomainCoeff<F>>(values: &mut [T], g: F) {
+    let mut pow = F::one();
+    for v in values.iter_mut() {
+        *v *= pow;
+        pow *= g;
+    }
+}
+
+/// reorders `values` so that index i ends up at the bit-reversal of i
+pub fn bit_reverse_permutation<T>(values: &mut [T]) {
+    let n = values.len();
+    if n <= 1 {
+        return;
+    }
+    debug_assert!(n.is_power_of_two());
+
+    let log_n = n.trailing_zeros();
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS - log_n);
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+}
+
+/// iterative Cooley-Tukey: bit-reverse, then log_n rounds of butterflies
+fn radix2_fft<F: FftField, T: DomainCoeff<F>>(values: &mut [T], omega: F, log_n: u32) {
+    let n = values.len();
+    bit_reverse_permutation(values);
+
+    let mut m = 1;
+    for _ in 0..log_n {
+        // primitive (2m)-th root of unity
+        let w_m = omega.pow([(n / (2 * m)) as u64]);
+        let twiddles = powers(F::one(), w_m, m);
+
+        for k in (0..n).step_by(2 * m) {
+            for (j, w) in twiddles.iter().enumerate() {
+                let mut t = values[k + j + m];
+                t *= *w;
+                let u = values[k + j];
+                values[k + j] = u + t;
+                values[k + j + m] = u - t;
+            }
+        }
+
+        m *= 2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kzg::polynomial::Polynomial;
+    use ark_bn254::G1Projective;
+    use ark_ec::ProjectiveCurve;
+    use ark_ff::{One, PrimeField, UniformRand};
+    use rand::{rngs::SmallRng, SeedableRng};
+
+    const RNG_SEED: [u8; 32] = [69; 32];
+
+    #[test]
+    fn test_domain_elements() {
+        let domain: EvaluationDomain = EvaluationDomain::new(5).unwrap();
+        assert_eq!(domain.size(), 8);
+        assert_eq!(domain.log_size(), 3);
+
+        let elements = domain.elements();
+        assert_eq!(elements[0], Scalar::one());
+        assert_eq!(elements[3], domain.element(3));
+        assert_eq!(domain.element(8), Scalar::one());
+        for x in elements {
+            assert!(domain.evaluate_vanishing_polynomial(x).is_zero());
+        }
+
+        assert!(EvaluationDomain::<Scalar>::new(1 << 29).is_none());
+    }
+
+    #[test]
+    fn test_fft_matches_eval() {
+        let mut rng = SmallRng::from_seed(RNG_SEED);
+
+        for num_coeffs in [1, 2, 7, 16, 33] {
+            let coeffs: Vec<Scalar> = (0..num_coeffs).map(|_| Scalar::rand(&mut rng)).collect();
+            let polynomial = Polynomial::new(coeffs.clone()).unwrap();
+            let domain = EvaluationDomain::new(num_coeffs).unwrap();
+
+            let evals = domain.fft(&coeffs).unwrap();
+            for (x, y) in domain.elements().into_iter().zip(evals.iter()) {
+                assert_eq!(polynomial.eval(x), *y);
+            }
+
+            let z = Scalar::rand(&mut rng);
+            assert_eq!(
+                domain.evaluate_from_evaluations(&evals, z).unwrap(),
+                polynomial.eval(z)
+            );
+            let x = domain.element(num_coeffs / 2);
+            assert_eq!(
+                domain.evaluate_from_evaluations(&evals, x).unwrap(),
+                polynomial.eval(x)
+            );
+
+            let coset_evals = domain.coset_fft(&coeffs).unwrap();
+            for (x, y) in domain.elements().into_iter().zip(coset_evals.iter()) {
+                assert_eq!(polynomial.eval(domain.coset_offset() * x), *y);
+            }
+
+            let mut padded = coeffs.clone();
+            padded.resize(domain.size(), Scalar::zero());
+            assert_eq!(domain.ifft(&evals).unwrap(), padded);
+            assert_eq!(domain.coset_ifft(&coset_evals).unwrap(), padded);
+        }
+    }
+
+    #[test]
+    fn test_fft_in_group() {
+        let mut rng = SmallRng::from_seed(RNG_SEED);
+        let domain = EvaluationDomain::new(8).unwrap();
+
+        let scalars: Vec<Scalar> = (0..8).map(|_| Scalar::rand(&mut rng)).collect();
+        let g = G1Projective::prime_subgroup_generator();
+        let mut points: Vec<G1Projective> = scalars.iter().map(|s| g.mul(s.into_repr())).collect();
+
+        domain.fft_in_place(&mut points).unwrap();
+        for (p, s) in points.iter().zip(domain.fft(&scalars).unwrap()) {
+            assert_eq!(*p, g.mul(s.into_repr()));
+        }
+
+        domain.ifft_in_place(&mut points).unwrap();
+        for (p, s) in points.iter().zip(scalars) {
+            assert_eq!(*p, g.mul(s.into_repr()));
+        }
+    }
+
+    #[test]
+    fn test_too_many_values() {
+        let domain = EvaluationDomain::new(8).unwrap();
+        let values = vec![Scalar::one(); 9];
+
+        assert!(matches!(
+            domain.evaluate_from_evaluations(&values, Scalar::one()),
+            Err(KZGError::DomainTooSmall(9, 8))
+        ));
+        for transform in [
+            EvaluationDomain::fft_in_place,
+            EvaluationDomain::ifft_in_place,
+            EvaluationDomain::coset_fft_in_place,
+            EvaluationDomain::coset_ifft_in_place,
+        ] {
+            let mut values = values.clone();
+            assert!(matches!(
+                transform(&domain, &mut values),
+                Err(KZGError::DomainTooSmall(9, 8))
+            ));
+            // left untouched
+            assert_eq!(values, vec![Scalar::one(); 9]);
+        }
+        assert!(domain.fft(&values).is_err());
+        assert!(domain.ifft(&values).is_err());
+    }
+}
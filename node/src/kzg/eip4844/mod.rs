@@ -0,0 +1,798 @@
+use super::domain::bit_reverse_permutation;
+use super::KZGError;
+use ark_bls12_381::{Bls12_381, Fq, Fq2, Fr, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ec::{msm::VariableBaseMSM, AffineCurve, PairingEngine, ProjectiveCurve};
+use ark_ff::{
+    batch_inversion, BigInteger256, BigInteger384, FftField, Field, One, PrimeField, Zero,
+};
+use sha2::{Digest, Sha256};
+use std::convert::TryInto;
+use std::path::Path;
+use std::sync::OnceLock;
+
+pub const FIELD_ELEMENTS_PER_BLOB: usize = 4096;
+pub const BYTES_PER_FIELD_ELEMENT: usize = 32;
+pub const BYTES_PER_BLOB: usize = FIELD_ELEMENTS_PER_BLOB * BYTES_PER_FIELD_ELEMENT;
+pub const BYTES_PER_G1: usize = 48;
+pub const BYTES_PER_G2: usize = 96;
+
+const FIAT_SHAMIR_PROTOCOL_DOMAIN: &[u8; 16] = b"FSBLOBVERIFY_V1_";
+const RANDOM_CHALLENGE_KZG_BATCH_DOMAIN: &[u8; 16] = b"RCKZGBATCH___V1_";
+
+/// a big-endian field element
+pub type Bytes32 = [u8; 32];
+/// a compressed G1 point: a commitment or a proof
+pub type Bytes48 = [u8; BYTES_PER_G1];
+
+/// the EIP-4844 trusted setup, in the layout the spec functions use
+#[derive(Clone, Debug)]
+pub struct TrustedSetup {
+    /// [L_i(s)]_1 for the lagrange polynomials of the blob domain, in bit-reversed order
+    g1_lagrange: Vec<G1Affine>,
+    /// [s^i]_2
+    g2_monomial: Vec<G2Affine>,
+    /// the roots of unity of the blob domain, in bit-reversed order
+    roots_of_unity: Vec<Fr>,
+}
+
+impl TrustedSetup {
+    /// the setup from the KZG ceremony used on mainnet, parsed once and cached
+    pub fn mainnet() -> &'static TrustedSetup {
+        static MAINNET: OnceLock<TrustedSetup> = OnceLock::new();
+        MAINNET.get_or_init(|| {
+            TrustedSetup::parse(include_str!("trusted_setup.txt"))
+                .expect("embedded trusted setup is valid")
+        })
+    }
+
+    /// parses the text format used by the c-kzg library: the number of G1 points (4096), the
+    /// number of G2 points (65), then the G1 lagrange points in natural order and the G2 monomial
+    /// points, each hex encoded and compressed
+    pub fn parse(text: &str) -> Result<TrustedSetup, KZGError> {
+        let invalid = |reason: &str| KZGError::InvalidParams(reason.to_string());
+
+        let mut tokens = text.split_whitespace();
+        let mut read_count = || {
+            tokens
+                .next()
+                .and_then(|token| token.parse::<usize>().ok())
+                .ok_or_else(|| invalid("missing point counts"))
+        };
+        let num_g1 = read_count()?;
+        let num_g2 = read_count()?;
+        if num_g1 != FIELD_ELEMENTS_PER_BLOB {
+            return Err(invalid("wrong number of G1 points"));
+        }
+        if num_g2 < 2 {
+            return Err(invalid("too few G2 points"));
+        }
+
+        let mut read_point = |len: usize| {
+            let bytes = tokens
+                .next()
+                .and_then(|token| hex::decode(token).ok())
+                .filter(|bytes| bytes.len() == len)
+                .ok_or_else(|| invalid("missing or malformed point"))?;
+            Ok::<_, KZGError>(bytes)
+        };
+
+        let mut g1_lagrange = (0..num_g1)
+            .map(|_| g1_from_bytes(&read_point(BYTES_PER_G1)?.try_into().unwrap()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let g2_monomial = (0..num_g2)
+            .map(|_| g2_from_bytes(&read_point(BYTES_PER_G2)?.try_into().unwrap()))
+            .collect::<Result<Vec<_>, _>>()?;
+        if tokens.next().is_some() {
+            return Err(invalid("trailing data after the G2 points"));
+        }
+        if g1_lagrange.iter().any(|g| g.is_zero()) || g2_monomial.iter().any(|h| h.is_zero()) {
+            return Err(invalid("point at infinity in the setup"));
+        }
+        bit_reverse_permutation(&mut g1_lagrange);
+
+        // the spec's primitive root of unity 7 is also the generator arkworks derives roots from
+        let root = Fr::get_root_of_unity(FIELD_ELEMENTS_PER_BLOB).unwrap();
+        let mut roots_of_unity = Vec::with_capacity(FIELD_ELEMENTS_PER_BLOB);
+        let mut current = Fr::one();
+        for _ in 0..FIELD_ELEMENTS_PER_BLOB {
+            roots_of_unity.push(current);
+            current *= root;
+        }
+        bit_reverse_permutation(&mut roots_of_unity);
+
+        Ok(TrustedSetup {
+            g1_lagrange,
+            g2_monomial,
+            roots_of_unity,
+        })
+    }
+
+    /// `parse` on the file at `path`
+    pub fn load_file<P: AsRef<Path>>(path: P) -> Result<TrustedSetup, KZGError> {
+        TrustedSetup::parse(&std::fs::read_to_string(path)?)
+    }
+
+    fn g1_lincomb(&self, scalars: &[Fr]) -> G1Projective {
+        g1_lincomb(&self.g1_lagrange, scalars)
+    }
+
+    /// the barycentric formula over the bit-reversed domain
+    fn evaluate_polynomial_in_evaluation_form(&self, polynomial: &[Fr], z: Fr) -> Fr {
+        if let Some(i) = self.roots_of_unity.iter().position(|w| *w == z) {
+            return polynomial[i];
+        }
+
+        let mut denominators: Vec<Fr> = self.roots_of_unity.iter().map(|w| z - w).collect();
+        batch_inversion(&mut denominators);
+
+        let sum = polynomial
+            .iter()
+            .zip(self.roots_of_unity.iter())
+            .zip(denominators.iter())
+            .map(|((p, w), d)| *p * w * d)
+            .sum::<Fr>();
+        let width = Fr::from(FIELD_ELEMENTS_PER_BLOB as u64);
+        sum * (z.pow([FIELD_ELEMENTS_PER_BLOB as u64]) - Fr::one()) * width.inverse().unwrap()
+    }
+
+    /// the proof for p(z) and y = p(z), with the quotient computed in evaluation form
+    fn compute_kzg_proof_impl(&self, polynomial: &[Fr], z: Fr) -> (G1Affine, Fr) {
+        let y = self.evaluate_polynomial_in_evaluation_form(polynomial, z);
+
+        let mut denominators: Vec<Fr> = self.roots_of_unity.iter().map(|w| *w - z).collect();
+        let z_index = denominators.iter().position(Zero::is_zero);
+        if let Some(m) = z_index {
+            denominators[m] = Fr::one();
+        }
+        batch_inversion(&mut denominators);
+
+        let mut quotient: Vec<Fr> = polynomial
+            .iter()
+            .zip(denominators.iter())
+            .map(|(p, d)| (*p - y) * d)
+            .collect();
+
+        if let Some(m) = z_index {
+            // q(z) = sum_{i != m} (p_i - y) * w_i / (z * (z - w_i)), and
+            // (p_i - y) * w_i / (z * (z - w_i)) = -q_i * w_i / z
+            quotient[m] = -quotient
+                .iter()
+                .zip(self.roots_of_unity.iter())
+                .enumerate()
+                .filter(|(i, _)| *i != m)
+                .map(|(_, (q, w))| *q * w)
+                .sum::<Fr>()
+                * z.inverse().unwrap();
+        }
+
+        (self.g1_lincomb(&quotient).into_affine(), y)
+    }
+
+    /// e(C - [y], -[1]) * e(W, [s - z]) == 1
+    fn verify_kzg_proof_impl(&self, commitment: G1Affine, z: Fr, y: Fr, proof: G1Affine) -> bool {
+        let g1 = G1Affine::prime_subgroup_generator();
+        let g2 = G2Affine::prime_subgroup_generator();
+
+        let x_minus_z = self.g2_monomial[1].into_projective() - g2.mul(z.into_repr());
+        let p_minus_y = commitment.into_projective() - g1.mul(y.into_repr());
+
+        pairing_check(&[
+            (p_minus_y, -g2.into_projective()),
+            (proof.into(), x_minus_z),
+        ])
+    }
+
+    /// checks all the openings at once with powers of a random challenge r:
+    /// e(sum r^i W_i, -[s]) * e(sum r^i (C_i - [y_i] + z_i W_i), [1]) == 1
+    fn verify_kzg_proof_batch(
+        &self,
+        commitments: &[G1Affine],
+        zs: &[Fr],
+        ys: &[Fr],
+        proofs: &[G1Affine],
+    ) -> bool {
+        let mut data = RANDOM_CHALLENGE_KZG_BATCH_DOMAIN.to_vec();
+        data.extend_from_slice(&(FIELD_ELEMENTS_PER_BLOB as u64).to_be_bytes());
+        data.extend_from_slice(&(commitments.len() as u64).to_be_bytes());
+        for i in 0..commitments.len() {
+            data.extend_from_slice(&g1_to_bytes(&commitments[i]));
+            data.extend_from_slice(&fr_to_bytes(&zs[i]));
+            data.extend_from_slice(&fr_to_bytes(&ys[i]));
+            data.extend_from_slice(&g1_to_bytes(&proofs[i]));
+        }
+        let r = hash_to_bls_field(&data);
+
+        let mut r_powers = Vec::with_capacity(commitments.len());
+        let mut r_power = Fr::one();
+        for _ in 0..commitments.len() {
+            r_powers.push(r_power);
+            r_power *= r;
+        }
+
+        let g1 = G1Affine::prime_subgroup_generator();
+        let proof_lincomb = g1_lincomb(proofs, &r_powers);
+        let proof_z_lincomb = g1_lincomb(
+            proofs,
+            &zs.iter()
+                .zip(r_powers.iter())
+                .map(|(z, r)| *z * r)
+                .collect::<Vec<Fr>>(),
+        );
+        let c_minus_ys = commitments
+            .iter()
+            .zip(ys.iter())
+            .map(|(c, y)| c.into_projective() - g1.mul(y.into_repr()))
+            .collect::<Vec<G1Projective>>();
+        let c_minus_y_lincomb = g1_lincomb(
+            &G1Projective::batch_normalization_into_affine(&c_minus_ys),
+            &r_powers,
+        );
+
+        pairing_check(&[
+            (proof_lincomb, -self.g2_monomial[1].into_projective()),
+            (
+                c_minus_y_lincomb + proof_z_lincomb,
+                G2Affine::prime_subgroup_generator().into_projective(),
+            ),
+        ])
+    }
+}
+
+/// commitment to the polynomial whose evaluations over the bit-reversed domain are `blob`
+pub fn blob_to_kzg_commitment(blob: &[u8], setup: &TrustedSetup) -> Result<Bytes48, KZGError> {
+    let polynomial = blob_to_polynomial(blob)?;
+    Ok(g1_to_bytes(&setup.g1_lincomb(&polynomial).into_affine()))
+}
+
+/// the proof for the evaluation of `blob`'s polynomial at `z`, along with the evaluation
+pub fn compute_kzg_proof(
+    blob: &[u8],
+    z: &Bytes32,
+    setup: &TrustedSetup,
+) -> Result<(Bytes48, Bytes32), KZGError> {
+    let polynomial = blob_to_polynomial(blob)?;
+    let z = bytes_to_bls_field(z)?;
+    let (proof, y) = setup.compute_kzg_proof_impl(&polynomial, z);
+    Ok((g1_to_bytes(&proof), fr_to_bytes(&y)))
+}
+
+/// the proof for `blob` at the Fiat-Shamir challenge derived from it and its commitment
+pub fn compute_blob_kzg_proof(
+    blob: &[u8],
+    commitment: &Bytes48,
+    setup: &TrustedSetup,
+) -> Result<Bytes48, KZGError> {
+    let polynomial = blob_to_polynomial(blob)?;
+    let commitment = g1_from_bytes(commitment)?;
+    let challenge = compute_challenge(blob, &commitment);
+    let (proof, _) = setup.compute_kzg_proof_impl(&polynomial, challenge);
+    Ok(g1_to_bytes(&proof))
+}
+
+/// checks that `proof` opens `commitment` to `y` at `z`
+pub fn verify_kzg_proof(
+    commitment: &Bytes48,
+    z: &Bytes32,
+    y: &Bytes32,
+    proof: &Bytes48,
+    setup: &TrustedSetup,
+) -> Result<bool, KZGError> {
+    let commitment = g1_from_bytes(commitment)?;
+    let z = bytes_to_bls_field(z)?;
+    let y = bytes_to_bls_field(y)?;
+    let proof = g1_from_bytes(proof)?;
+    Ok(setup.verify_kzg_proof_impl(commitment, z, y, proof))
+}
+
+/// checks a proof from `compute_blob_kzg_proof`
+pub fn verify_blob_kzg_proof(
+    blob: &[u8],
+    commitment: &Bytes48,
+    proof: &Bytes48,
+    setup: &TrustedSetup,
+) -> Result<bool, KZGError> {
+    let commitment = g1_from_bytes(commitment)?;
+    let polynomial = blob_to_polynomial(blob)?;
+    let challenge = compute_challenge(blob, &commitment);
+    let y = setup.evaluate_polynomial_in_evaluation_form(&polynomial, challenge);
+    let proof = g1_from_bytes(proof)?;
+    Ok(setup.verify_kzg_proof_impl(commitment, challenge, y, proof))
+}
+
+/// `verify_blob_kzg_proof` for many blobs with a single pairing check
+pub fn verify_blob_kzg_proof_batch<B: AsRef<[u8]>>(
+    blobs: &[B],
+    commitments: &[Bytes48],
+    proofs: &[Bytes48],
+    setup: &TrustedSetup,
+) -> Result<bool, KZGError> {
+    if blobs.len() != commitments.len() || blobs.len() != proofs.len() {
+        return Err(KZGError::InvalidInput(format!(
+            "{} blobs, {} commitments and {} proofs",
+            blobs.len(),
+            commitments.len(),
+            proofs.len()
+        )));
+    }
+
+    let mut commitment_points = Vec::with_capacity(blobs.len());
+    let mut challenges = Vec::with_capacity(blobs.len());
+    let mut ys = Vec::with_capacity(blobs.len());
+    let mut proof_points = Vec::with_capacity(blobs.len());
+    for ((blob, commitment), proof) in blobs.iter().zip(commitments).zip(proofs) {
+        let commitment = g1_from_bytes(commitment)?;
+        let polynomial = blob_to_polynomial(blob.as_ref())?;
+        let challenge = compute_challenge(blob.as_ref(), &commitment);
+
+        ys.push(setup.evaluate_polynomial_in_evaluation_form(&polynomial, challenge));
+        challenges.push(challenge);
+        commitment_points.push(commitment);
+        proof_points.push(g1_from_bytes(proof)?);
+    }
+
+    Ok(setup.verify_kzg_proof_batch(&commitment_points, &challenges, &ys, &proof_points))
+}
+
+/// the blob's field elements, which must each be canonical
+fn blob_to_polynomial(blob: &[u8]) -> Result<Vec<Fr>, KZGError> {
+    if blob.len() != BYTES_PER_BLOB {
+        return Err(KZGError::InvalidBlob(format!(
+            "expected {} bytes, got {}",
+            BYTES_PER_BLOB,
+            blob.len()
+        )));
+    }
+
+    blob.chunks(BYTES_PER_FIELD_ELEMENT)
+        .map(|chunk| bytes_to_bls_field(chunk.try_into().unwrap()))
+        .collect()
+}
+
+/// sha256(domain | degree as u128 | blob | commitment), reduced
+fn compute_challenge(blob: &[u8], commitment: &G1Affine) -> Fr {
+    let mut data = FIAT_SHAMIR_PROTOCOL_DOMAIN.to_vec();
+    data.extend_from_slice(&(FIELD_ELEMENTS_PER_BLOB as u128).to_be_bytes());
+    data.extend_from_slice(blob);
+    data.extend_from_slice(&g1_to_bytes(commitment));
+    hash_to_bls_field(&data)
+}
+
+fn hash_to_bls_field(data: &[u8]) -> Fr {
+    Fr::from_be_bytes_mod_order(&Sha256::digest(data))
+}
+
+fn bytes_to_bls_field(bytes: &Bytes32) -> Result<Fr, KZGError> {
+    let mut limbs = [0u64; 4];
+    for (limb, chunk) in limbs.iter_mut().rev().zip(bytes.chunks(8)) {
+        *limb = u64::from_be_bytes(chunk.try_into().unwrap());
+    }
+    Fr::from_repr(BigInteger256::new(limbs))
+        .ok_or_else(|| KZGError::InvalidInput("field element is not canonical".to_string()))
+}
+
+fn fr_to_bytes(fr: &Fr) -> Bytes32 {
+    let mut bytes = [0u8; 32];
+    for (chunk, limb) in bytes.chunks_mut(8).zip(fr.into_repr().0.iter().rev()) {
+        chunk.copy_from_slice(&limb.to_be_bytes());
+    }
+    bytes
+}
+
+fn fq_from_bytes(bytes: &[u8]) -> Option<Fq> {
+    let mut limbs = [0u64; 6];
+    for (limb, chunk) in limbs.iter_mut().rev().zip(bytes.chunks(8)) {
+        *limb = u64::from_be_bytes(chunk.try_into().unwrap());
+    }
+    Fq::from_repr(BigInteger384::new(limbs))
+}
+
+fn fq_to_bytes(fq: &Fq) -> [u8; 48] {
+    let mut bytes = [0u8; 48];
+    for (chunk, limb) in bytes.chunks_mut(8).zip(fq.into_repr().0.iter().rev()) {
+        chunk.copy_from_slice(&limb.to_be_bytes());
+    }
+    bytes
+}
+
+// the zcash serialization: big-endian x with three flags in the top bits of the first byte
+const COMPRESSED_FLAG: u8 = 0x80;
+const INFINITY_FLAG: u8 = 0x40;
+const SIGN_FLAG: u8 = 0x20;
+
+/// splits off the flags and checks the encoding of infinity, returning `None` for infinity and
+/// the x coordinate bytes and sign (whether y is the larger root) otherwise
+fn decode_flags<const N: usize>(bytes: &[u8; N]) -> Result<Option<([u8; N], bool)>, KZGError> {
+    let invalid = |reason: &str| KZGError::InvalidInput(reason.to_string());
+    let flags = bytes[0];
+    if flags & COMPRESSED_FLAG == 0 {
+        return Err(invalid("point is not compressed"));
+    }
+
+    if flags & INFINITY_FLAG != 0 {
+        if flags != COMPRESSED_FLAG | INFINITY_FLAG || bytes[1..].iter().any(|b| *b != 0) {
+            return Err(invalid("malformed point at infinity"));
+        }
+        return Ok(None);
+    }
+
+    let mut x = *bytes;
+    x[0] &= !(COMPRESSED_FLAG | INFINITY_FLAG | SIGN_FLAG);
+    Ok(Some((x, flags & SIGN_FLAG != 0)))
+}
+
+fn g1_from_bytes(bytes: &Bytes48) -> Result<G1Affine, KZGError> {
+    let invalid = |reason: &str| KZGError::InvalidInput(reason.to_string());
+    let (x, greatest) = match decode_flags(bytes)? {
+        None => return Ok(G1Affine::zero()),
+        Some(decoded) => decoded,
+    };
+
+    let x = fq_from_bytes(&x).ok_or_else(|| invalid("G1 coordinate is not canonical"))?;
+    let point = G1Affine::get_point_from_x(x, greatest)
+        .ok_or_else(|| invalid("G1 point is not on the curve"))?;
+    if !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(invalid("G1 point is not in the prime-order subgroup"));
+    }
+    Ok(point)
+}
+
+fn g1_to_bytes(point: &G1Affine) -> Bytes48 {
+    if point.is_zero() {
+        let mut bytes = [0u8; BYTES_PER_G1];
+        bytes[0] = COMPRESSED_FLAG | INFINITY_FLAG;
+        return bytes;
+    }
+
+    let mut bytes = fq_to_bytes(&point.x);
+    bytes[0] |= COMPRESSED_FLAG;
+    if point.y > -point.y {
+        bytes[0] |= SIGN_FLAG;
+    }
+    bytes
+}
+
+/// x is written as c1 | c0
+fn g2_from_bytes(bytes: &[u8; BYTES_PER_G2]) -> Result<G2Affine, KZGError> {
+    let invalid = |reason: &str| KZGError::InvalidInput(reason.to_string());
+    let (x, greatest) = match decode_flags(bytes)? {
+        None => return Ok(G2Affine::zero()),
+        Some(decoded) => decoded,
+    };
+
+    let (c1, c0) = x.split_at(48);
+    let x = match (fq_from_bytes(c0), fq_from_bytes(c1)) {
+        (Some(c0), Some(c1)) => Fq2::new(c0, c1),
+        _ => return Err(invalid("G2 coordinate is not canonical")),
+    };
+    let point = G2Affine::get_point_from_x(x, greatest)
+        .ok_or_else(|| invalid("G2 point is not on the curve"))?;
+    if !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(invalid("G2 point is not in the prime-order subgroup"));
+    }
+    Ok(point)
+}
+
+fn g1_lincomb(points: &[G1Affine], scalars: &[Fr]) -> G1Projective {
+    let scalars = scalars.iter().map(|s| s.into_repr()).collect::<Vec<_>>();
+    VariableBaseMSM::multi_scalar_mul(points, &scalars)
+}
+
+/// prod e(a_i, b_i) == 1
+fn pairing_check(pairs: &[(G1Projective, G2Projective)]) -> bool {
+    let prepared = pairs
+        .iter()
+        .map(|(a, b)| (a.into_affine().into(), b.into_affine().into()))
+        .collect::<Vec<_>>();
+    Bls12_381::product_of_pairings(&prepared).is_one()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::FrParameters;
+    use ark_ff::{BigInteger, FpParameters};
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+    use serde::Deserialize;
+    use std::fs;
+    use std::path::PathBuf;
+
+    const RNG_SEED: [u8; 32] = [69; 32];
+
+    /// official vectors from the consensus spec tests (general/deneb/kzg), see test_vectors/README
+    fn test_vectors<T: serde::de::DeserializeOwned>(suite: &str) -> Vec<(String, TestCase<T>)> {
+        let dir: PathBuf = [
+            env!("CARGO_MANIFEST_DIR"),
+            "src/kzg/eip4844/test_vectors",
+            suite,
+        ]
+        .iter()
+        .collect();
+        let mut cases = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| {
+                let path = entry.unwrap().path();
+                let name = path.file_name().unwrap().to_string_lossy().into_owned();
+                let yaml = fs::read_to_string(path.join("data.yaml")).unwrap();
+                (name, serde_yaml::from_str(&yaml).unwrap())
+            })
+            .collect::<Vec<_>>();
+        assert!(!cases.is_empty());
+        cases.sort_by(|a, b| a.0.cmp(&b.0));
+        cases
+    }
+
+    #[derive(Deserialize)]
+    struct TestCase<T> {
+        input: T,
+        output: Option<bool>,
+    }
+
+    #[derive(Deserialize)]
+    struct VerifyKzgProof {
+        commitment: String,
+        z: String,
+        y: String,
+        proof: String,
+    }
+
+    #[derive(Deserialize)]
+    struct VerifyBlobKzgProof {
+        blob: String,
+        commitment: String,
+        proof: String,
+    }
+
+    #[derive(Deserialize)]
+    struct VerifyBlobKzgProofBatch {
+        blobs: Vec<String>,
+        commitments: Vec<String>,
+        proofs: Vec<String>,
+    }
+
+    fn decode(hex_str: &str) -> Vec<u8> {
+        hex::decode(hex_str.strip_prefix("0x").unwrap()).unwrap()
+    }
+
+    /// fixed-size inputs of the wrong length are errors, like any other invalid encoding
+    fn decode_array<const N: usize>(hex_str: &str) -> Result<[u8; N], KZGError> {
+        decode(hex_str)
+            .try_into()
+            .map_err(|_| KZGError::InvalidInput("wrong length".to_string()))
+    }
+
+    fn random_blob(rng: &mut SmallRng) -> Vec<u8> {
+        (0..FIELD_ELEMENTS_PER_BLOB)
+            .flat_map(|_| fr_to_bytes(&Fr::from(rng.gen::<u128>())))
+            .collect()
+    }
+
+    #[test]
+    fn test_trusted_setup() {
+        let setup = TrustedSetup::mainnet();
+        assert_eq!(setup.g1_lagrange.len(), FIELD_ELEMENTS_PER_BLOB);
+        assert_eq!(setup.g2_monomial.len(), 65);
+        assert_eq!(setup.g2_monomial[0], G2Affine::prime_subgroup_generator());
+
+        // the lagrange polynomials sum to 1
+        let sum = setup.g1_lincomb(&vec![Fr::one(); FIELD_ELEMENTS_PER_BLOB]);
+        assert_eq!(sum.into_affine(), G1Affine::prime_subgroup_generator());
+
+        // primitive 4096th root of unity, as in the spec
+        let mut exponent = FrParameters::MODULUS;
+        exponent.sub_noborrow(&BigInteger256::from(1));
+        exponent.divn(12);
+        let root = Fr::from(7u64).pow(exponent);
+        assert_eq!(setup.roots_of_unity[0], Fr::one());
+        assert_eq!(setup.roots_of_unity[FIELD_ELEMENTS_PER_BLOB / 2], root);
+
+        for point in [setup.g1_lagrange[1], -setup.g1_lagrange[2]] {
+            assert_eq!(g1_from_bytes(&g1_to_bytes(&point)).unwrap(), point);
+        }
+        assert!(TrustedSetup::parse("4096 65").is_err());
+    }
+
+    #[test]
+    fn test_verify_kzg_proof_vectors() {
+        let setup = TrustedSetup::mainnet();
+
+        for (name, case) in test_vectors::<VerifyKzgProof>("verify_kzg_proof") {
+            let input = case.input;
+            let result = (|| {
+                verify_kzg_proof(
+                    &decode_array(&input.commitment)?,
+                    &decode_array(&input.z)?,
+                    &decode_array(&input.y)?,
+                    &decode_array(&input.proof)?,
+                    setup,
+                )
+            })();
+            assert_eq!(result.ok(), case.output, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_verify_blob_kzg_proof_vectors() {
+        let setup = TrustedSetup::mainnet();
+
+        for (name, case) in test_vectors::<VerifyBlobKzgProof>("verify_blob_kzg_proof") {
+            let input = case.input;
+            let blob = decode(&input.blob);
+            let result = (|| {
+                let commitment = decode_array(&input.commitment)?;
+                let proof = decode_array(&input.proof)?;
+                let valid = verify_blob_kzg_proof(&blob, &commitment, &proof, setup)?;
+
+                // the vectors' valid commitments and proofs are the ones the prover computes
+                if valid {
+                    assert_eq!(blob_to_kzg_commitment(&blob, setup)?, commitment);
+                    assert_eq!(compute_blob_kzg_proof(&blob, &commitment, setup)?, proof);
+                }
+                Ok::<_, KZGError>(valid)
+            })();
+            assert_eq!(result.ok(), case.output, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_verify_blob_kzg_proof_batch_vectors() {
+        let setup = TrustedSetup::mainnet();
+
+        for (name, case) in test_vectors::<VerifyBlobKzgProofBatch>("verify_blob_kzg_proof_batch") {
+            let input = case.input;
+            let blobs = input.blobs.iter().map(|b| decode(b)).collect::<Vec<_>>();
+            let result = (|| {
+                let commitments = input
+                    .commitments
+                    .iter()
+                    .map(|c| decode_array(c))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let proofs = input
+                    .proofs
+                    .iter()
+                    .map(|p| decode_array(p))
+                    .collect::<Result<Vec<_>, _>>()?;
+                verify_blob_kzg_proof_batch(&blobs, &commitments, &proofs, setup)
+            })();
+            assert_eq!(result.ok(), case.output, "{}", name);
+        }
+    }
+
+    /// the valid blobs of the consensus spec's kzg_4844 test generator
+    fn spec_valid_blobs() -> Vec<Vec<u8>> {
+        let blob = |f: &dyn Fn(u64) -> Fr| -> Vec<u8> {
+            (0..FIELD_ELEMENTS_PER_BLOB as u64)
+                .flat_map(|n| fr_to_bytes(&f(n)))
+                .collect()
+        };
+        vec![
+            blob(&|_| Fr::zero()),
+            blob(&|_| Fr::from(2u64)),
+            blob(&|n| Fr::from(2u64).pow([n + 256])),
+            blob(&|n| Fr::from(3u64).pow([n + 256])),
+            blob(&|n| Fr::from(5u64).pow([n + 256])),
+            blob(&|_| -Fr::one()),
+            blob(&|n| if n == 3211 { Fr::one() } else { Fr::zero() }),
+        ]
+    }
+
+    /// the official `compute_kzg_proof` suite isn't checked in, but the valid `verify_kzg_proof`
+    /// cases are its outputs for `spec_valid_blobs`, so check the prover against those, and
+    /// against the generator's invalid inputs
+    #[test]
+    fn test_compute_vectors() {
+        let setup = TrustedSetup::mainnet();
+        let blobs = spec_valid_blobs();
+        let commitments = blobs
+            .iter()
+            .map(|blob| blob_to_kzg_commitment(blob, setup).unwrap())
+            .collect::<Vec<_>>();
+
+        let mut used = vec![false; blobs.len()];
+        let mut invalid_commitments = Vec::new();
+        for (name, case) in test_vectors::<VerifyKzgProof>("verify_kzg_proof") {
+            let input = case.input;
+            if name.contains("invalid_commitment") {
+                invalid_commitments.push(input.commitment.clone());
+            }
+            if case.output != Some(true) {
+                continue;
+            }
+
+            let commitment: Bytes48 = decode_array(&input.commitment).unwrap();
+            let i = commitments
+                .iter()
+                .position(|c| *c == commitment)
+                .unwrap_or_else(|| panic!("{}: no blob for the commitment", name));
+            used[i] = true;
+
+            let z = decode_array(&input.z).unwrap();
+            let expected = (
+                decode_array(&input.proof).unwrap(),
+                decode_array(&input.y).unwrap(),
+            );
+            assert_eq!(
+                compute_kzg_proof(&blobs[i], &z, setup).unwrap(),
+                expected,
+                "{}",
+                name
+            );
+        }
+        assert!(used.iter().all(|used| *used));
+        assert!(!invalid_commitments.is_empty());
+
+        let modulus = FrParameters::MODULUS.to_bytes_be();
+        let mut invalid_close = vec![0u8; BYTES_PER_BLOB];
+        invalid_close[2111 * 32..2112 * 32].copy_from_slice(&modulus);
+        let mut plus_one = blobs[2].clone();
+        plus_one.push(0);
+        let invalid_blobs = [
+            vec![0xff; BYTES_PER_BLOB],
+            invalid_close,
+            plus_one,
+            blobs[2][1..].to_vec(),
+        ];
+        let z = fr_to_bytes(&Fr::from(2u64));
+        for blob in &invalid_blobs {
+            assert!(blob_to_kzg_commitment(blob, setup).is_err());
+            assert!(compute_kzg_proof(blob, &z, setup).is_err());
+            assert!(compute_blob_kzg_proof(blob, &commitments[0], setup).is_err());
+        }
+
+        let mut modulus_plus_one = FrParameters::MODULUS;
+        modulus_plus_one.add_nocarry(&BigInteger256::from(1));
+        let mut uint256_mid = [0xff; 32];
+        uint256_mid[16..].copy_from_slice(&[0; 16]);
+        let invalid_zs = [
+            modulus.try_into().unwrap(),
+            modulus_plus_one.to_bytes_be().try_into().unwrap(),
+            [0xff; 32],
+            uint256_mid,
+        ];
+        for z in &invalid_zs {
+            assert!(compute_kzg_proof(&blobs[2], z, setup).is_err());
+        }
+
+        for commitment in &invalid_commitments {
+            let result = decode_array(commitment)
+                .and_then(|commitment| compute_blob_kzg_proof(&blobs[2], &commitment, setup));
+            assert!(result.is_err(), "{}", commitment);
+        }
+    }
+
+    #[test]
+    fn test_compute_kzg_proof() {
+        let mut rng = SmallRng::from_seed(RNG_SEED);
+        let setup = TrustedSetup::mainnet();
+
+        let blob = random_blob(&mut rng);
+        let commitment = blob_to_kzg_commitment(&blob, setup).unwrap();
+        let polynomial = blob_to_polynomial(&blob).unwrap();
+
+        // outside the domain, and at the domain point holding the blob's fourth element
+        for z in [Fr::from(rng.gen::<u128>()), setup.roots_of_unity[3]] {
+            let z = fr_to_bytes(&z);
+            let (proof, y) = compute_kzg_proof(&blob, &z, setup).unwrap();
+            assert!(verify_kzg_proof(&commitment, &z, &y, &proof, setup).unwrap());
+
+            let wrong_y = fr_to_bytes(&(bytes_to_bls_field(&y).unwrap() + Fr::one()));
+            assert!(!verify_kzg_proof(&commitment, &z, &wrong_y, &proof, setup).unwrap());
+        }
+        let (_, y) =
+            compute_kzg_proof(&blob, &fr_to_bytes(&setup.roots_of_unity[3]), setup).unwrap();
+        assert_eq!(bytes_to_bls_field(&y).unwrap(), polynomial[3]);
+
+        let proof = compute_blob_kzg_proof(&blob, &commitment, setup).unwrap();
+        assert!(verify_blob_kzg_proof(&blob, &commitment, &proof, setup).unwrap());
+
+        let mut non_canonical = blob.clone();
+        non_canonical[..32].copy_from_slice(&[0xff; 32]);
+        assert!(matches!(
+            blob_to_kzg_commitment(&non_canonical, setup),
+            Err(KZGError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            blob_to_kzg_commitment(&blob[1..], setup),
+            Err(KZGError::InvalidBlob(_))
+        ));
+    }
+}
@@ -0,0 +1,85 @@
+use super::domain::EvaluationDomain;
+use super::polynomial::Polynomial;
+use super::{KZGWitness, Opening};
+use ark_bn254::Bn254;
+use ark_ec::{PairingEngine, ProjectiveCurve};
+use ark_ff::Zero;
+
+/// opening proofs for every point of an evaluation domain, as computed by
+/// `KZGProver::open_all_on_domain`. the witness at index i opens the polynomial at w^i.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OpeningCache<E: PairingEngine = Bn254> {
+    pub domain: EvaluationDomain<E::Fr>,
+    /// p(w^i)
+    pub evaluations: Vec<E::Fr>,
+    /// commitment to (p(X) - p(w^i)) / (X - w^i)
+    pub witnesses: Vec<KZGWitness<E>>,
+}
+
+impl<E: PairingEngine> OpeningCache<E> {
+    pub fn len(&self) -> usize {
+        self.witnesses.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.witnesses.is_empty()
+    }
+
+    /// the point (w^index, p(w^index)) and its witness
+    pub fn opening(&self, index: usize) -> Option<Opening<E>> {
+        let witness = *self.witnesses.get(index)?;
+        Some((
+            (self.domain.element(index), self.evaluations[index]),
+            witness,
+        ))
+    }
+}
+
+/// computes [q_z(s)] for every z = w^i in `domain` with O(n log n) group operations.
+///
+/// writing p = sum c_j X^j of degree d and S_j = [s^j]_1, the quotient by (X - z) commits to
+///     sum_t z^t h_t,  where h_t = sum_{j=t+1}^{d} c_j S_{j-t-1}
+/// the h_t are a Toeplitz matrix-vector product, i.e. the tail of the convolution of the
+/// coefficients with the reversed powers (c * a)[d + t] for a_u = S_{d-1-u}, computed by FFTs in
+/// G1. the witnesses are then just the DFT of h over `domain`.
+///
+/// `gs` must hold at least `degree` powers.
+pub(crate) fn all_witnesses<E: PairingEngine>(
+    gs: &[E::G1Projective],
+    polynomial: &Polynomial<E::Fr>,
+    domain: &EvaluationDomain<E::Fr>,
+) -> Vec<KZGWitness<E>> {
+    let d = polynomial.degree();
+    if d == 0 {
+        return vec![KZGWitness::<E>::zero(); domain.size()];
+    }
+
+    let conv_domain = EvaluationDomain::new(2 * d).unwrap();
+
+    let mut reversed_powers = gs[..d]
+        .iter()
+        .rev()
+        .copied()
+        .collect::<Vec<E::G1Projective>>();
+    // d powers and d + 1 coefficients both fit in 2d points
+    let fits = "the convolution domain fits both operands";
+    conv_domain.fft_in_place(&mut reversed_powers).expect(fits);
+
+    let coeffs = conv_domain.fft(polynomial.slice_coeffs()).expect(fits);
+    reversed_powers
+        .iter_mut()
+        .zip(coeffs.iter())
+        .for_each(|(a, c)| *a *= *c);
+    conv_domain.ifft_in_place(&mut reversed_powers).expect(fits);
+
+    // w^(n+t) = w^t, so h only matters mod X^n
+    let mut h = vec![E::G1Projective::zero(); domain.size().min(d)];
+    for (t, h_t) in reversed_powers[d..2 * d].iter().enumerate() {
+        h[t % domain.size()] += h_t;
+    }
+
+    domain
+        .fft_in_place(&mut h)
+        .expect("h is folded to the domain size");
+    E::G1Projective::batch_normalization_into_affine(&h)
+}
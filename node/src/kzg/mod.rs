@@ -1,34 +1,76 @@
-use ark_bn254::{
-    g1::{G1_GENERATOR_X, G1_GENERATOR_Y},
-    g2::{G2_GENERATOR_X, G2_GENERATOR_Y},
-    Bn254, Fr as Scalar, G1Affine, G1Projective, G2Affine, G2Projective,
-};
-use ark_ec::{msm::VariableBaseMSM, PairingEngine, ProjectiveCurve};
-use ark_ff::{BigInteger256, PrimeField, Zero};
+use ark_bn254::{Bn254, Fr as Scalar};
+use ark_ec::{msm::VariableBaseMSM, AffineCurve, PairingEngine, ProjectiveCurve};
+use ark_ff::{batch_inversion, Field, One, PrimeField, UniformRand, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write};
+use rand::Rng;
+use sha2::{Digest, Sha256};
 use thiserror::Error;
 
+pub mod abi;
+pub mod blob;
+pub mod bundle;
+pub mod domain;
+pub mod eip4844;
+pub mod fk20;
 pub mod polynomial;
+pub mod ptau;
+pub mod serialization;
+pub mod solidity;
+pub mod subproduct_tree;
+pub mod transcript;
 
+use domain::EvaluationDomain;
+use fk20::OpeningCache;
 use polynomial::Polynomial;
+use subproduct_tree::SubproductTree;
+use transcript::{Transcript, TranscriptHash, TranscriptPoint};
 
 use std::fmt::Debug;
 
+/// the scheme works over any pairing engine `E`. the default type parameters keep BN254, the
+/// curve with EVM precompiles, as the default everywhere.
 #[derive(Debug, Clone)]
-pub struct KZGProver<'params> {
-    parameters: &'params KZGParams,
+pub struct KZGProver<'params, E: PairingEngine = Bn254> {
+    parameters: &'params KZGParams<E>,
 }
 
 /// parameters from tested setup
-#[derive(Clone, Debug)]
-pub struct KZGParams {
+#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct KZGParams<E: PairingEngine = Bn254> {
     /// g, g^alpha^1, g^alpha^2, ...
-    pub gs: Vec<G1Projective>,
+    pub gs: Vec<E::G1Projective>,
     /// h, h^alpha^1, h^alpha^2, ...
-    pub hs: Vec<G2Projective>,
+    pub hs: Vec<E::G2Projective>,
+    /// g^L_0(alpha), g^L_1(alpha), ... for the lagrange polynomials of a roots-of-unity domain,
+    /// see `KZGParams::with_lagrange_basis`
+    pub lagrange_gs: Option<Vec<E::G1Projective>>,
+    /// h, h^alpha^1, h^alpha^2, ... for a second G1 generator h with unknown discrete log to g,
+    /// for hiding commitments, see `ptau::load_ptau_blinding_powers`
+    pub blinding_gs: Option<Vec<E::G1Projective>>,
 }
 
-pub type KZGCommitment = G1Affine;
-pub type KZGWitness = G1Affine;
+pub type KZGCommitment<E = Bn254> = <E as PairingEngine>::G1Affine;
+pub type KZGWitness<E = Bn254> = <E as PairingEngine>::G1Affine;
+/// an evaluation point (x, y) and the witness for it
+pub type Opening<E = Bn254> = (
+    (<E as PairingEngine>::Fr, <E as PairingEngine>::Fr),
+    KZGWitness<E>,
+);
+
+/// a claim (x, y, commitment, witness) that the committed polynomial takes y at x, see
+/// `KZGVerifier::verify_eval_batch`
+pub type EvalClaim<E = Bn254> = (
+    <E as PairingEngine>::Fr,
+    <E as PairingEngine>::Fr,
+    KZGCommitment<E>,
+    KZGWitness<E>,
+);
+
+/// a lagrange basis and the domain it is over
+type LagrangeBasis<'params, E> = (
+    &'params [<E as PairingEngine>::G1Projective],
+    EvaluationDomain<<E as PairingEngine>::Fr>,
+);
 
 #[derive(Error, Debug)]
 pub enum KZGError {
@@ -40,130 +82,858 @@ pub enum KZGError {
     BatchOpeningZeroRemainder,
     #[error("polynomial degree too large")]
     PolynomialDegreeTooLarge,
+    #[error("empty input")]
+    EmptyInput,
+    #[error("duplicate interpolation points")]
+    DuplicatePoints,
+    #[error("SRS too small: {0} powers needed but only {1} available")]
+    SrsTooSmall(usize, usize),
+    #[error("{0} points but {1} values")]
+    PointMismatch(usize, usize),
+    #[error("{0} values do not fit in a domain of size {1}")]
+    DomainTooSmall(usize, usize),
+    #[error("parameters have no lagrange basis")]
+    NoLagrangeBasis,
+    #[error("parameters have no blinding powers")]
+    NoBlindingPowers,
+    #[error("invalid blob: {0}")]
+    InvalidBlob(String),
+    #[error("invalid input: {0}")]
+    InvalidInput(String),
+    #[error("invalid parameters: {0}")]
+    InvalidParams(String),
+    #[error("invalid proof bundle: {0}")]
+    InvalidBundle(String),
+    #[error("invalid ptau file: {0}")]
+    InvalidPtauFile(String),
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
 }
 
+/// BN254 parameters for the secret `s`, see `KZGParams::from_secret`
 pub fn setup(s: Scalar, num_coeffs: usize) -> KZGParams {
-    let generator_g1: G1Affine = G1Affine::new(G1_GENERATOR_X, G1_GENERATOR_Y, false);
-    let generator_g2: G2Affine = G2Affine::new(G2_GENERATOR_X, G2_GENERATOR_Y, false);
+    KZGParams::from_secret(s, num_coeffs)
+}
 
-    let mut gs = vec![generator_g1.into(); num_coeffs];
-    let mut hs = vec![generator_g2.into(); num_coeffs];
+impl<E: PairingEngine> KZGParams<E> {
+    /// `num_coeffs` powers of the secret `s` in G1 and G2. knowing `s` breaks the scheme, so this
+    /// is only meant for tests; see `ptau` for loading real parameters.
+    pub fn from_secret(s: E::Fr, num_coeffs: usize) -> Self {
+        let mut gs = vec![E::G1Affine::prime_subgroup_generator().into_projective(); num_coeffs];
+        let mut hs = vec![E::G2Affine::prime_subgroup_generator().into_projective(); num_coeffs];
 
-    let mut curr: G1Projective = gs[0];
-    for g in gs.iter_mut().skip(1) {
-        *g = curr.mul(&s.into_repr());
-        curr = *g;
+        let mut curr: E::G1Projective = gs[0];
+        for g in gs.iter_mut().skip(1) {
+            *g = curr.mul(s.into_repr());
+            curr = *g;
+        }
+
+        let mut curr: E::G2Projective = hs[0];
+        for h in hs.iter_mut().skip(1) {
+            *h = curr.mul(s.into_repr());
+            curr = *h;
+        }
+
+        KZGParams {
+            gs,
+            hs,
+            lagrange_gs: None,
+            blinding_gs: None,
+        }
     }
 
-    let mut curr: G2Projective = hs[0];
-    for h in hs.iter_mut().skip(1) {
-        *h = curr.mul(&s.into_repr());
-        curr = *h;
+    /// derives the lagrange basis g^L_i(alpha) over `domain` from the first `domain.size()` powers
+    /// in `gs` with an inverse NTT in G1, so that `KZGProver::commit_evaluations` can commit to
+    /// evaluations over the domain without interpolating them first
+    pub fn with_lagrange_basis(
+        mut self,
+        domain: &EvaluationDomain<E::Fr>,
+    ) -> Result<Self, KZGError> {
+        if self.gs.len() < domain.size() {
+            return Err(KZGError::PolynomialDegreeTooLarge);
+        }
+
+        // L_i(X) = 1/n sum_j w^(-ij) X^j, so g^L_i(alpha) is the inverse DFT of the powers
+        let mut lagrange_gs = self.gs[..domain.size()].to_vec();
+        domain.ifft_in_place(&mut lagrange_gs)?;
+
+        self.lagrange_gs = Some(lagrange_gs);
+        Ok(self)
     }
 
-    KZGParams { gs, hs }
+    /// adds the blinding powers h^alpha^i for h = g^gamma. like `from_secret`, knowing gamma
+    /// breaks hiding, so this is only meant for tests; `ptau::load_ptau_blinding_powers` loads
+    /// real ones.
+    pub fn with_insecure_blinding_powers(mut self, gamma: E::Fr) -> Self {
+        let gamma = gamma.into_repr();
+        self.blinding_gs = Some(self.gs.iter().map(|g| g.mul(gamma)).collect());
+        self
+    }
+
+    /// the domain of the lagrange basis, if there is one
+    pub fn lagrange_domain(&self) -> Option<EvaluationDomain<E::Fr>> {
+        self.lagrange_gs
+            .as_ref()
+            .and_then(|lagrange_gs| EvaluationDomain::new(lagrange_gs.len()))
+    }
+
+    /// SHA-256 of the compressed `gs` and `hs`, followed by `blinding_gs` if there are any,
+    /// identifying the SRS in a `bundle::ProofBundle`. the blinding powers come from a second
+    /// generator, so they're part of the SRS, but params without them hash as before. the
+    /// lagrange basis is derived from `gs` and isn't included.
+    pub fn srs_hash(&self) -> [u8; 32] {
+        let mut bytes = Vec::new();
+        self.gs
+            .serialize(&mut bytes)
+            .and_then(|_| self.hs.serialize(&mut bytes))
+            .and_then(|_| match &self.blinding_gs {
+                Some(blinding_gs) => blinding_gs.serialize(&mut bytes),
+                None => Ok(()),
+            })
+            .expect("serializing to a Vec can't fail");
+        Sha256::digest(&bytes).into()
+    }
+
+    /// checks that the parameters are structurally a powers-of-tau SRS: every element is a
+    /// non-identity point of the prime-order subgroup, `gs[0]` and `hs[0]` are the standard
+    /// generators, and successive powers use the same secret, i.e.
+    ///     e(gs[i+1], hs[0]) == e(gs[i], hs[1])  and  e(gs[0], hs[i+1]) == e(gs[1], hs[i])
+    /// the pairing checks are batched with random linear combinations, and only re-run one by one
+    /// to find the first mismatch if the batch fails.
+    pub fn validate(&self) -> Result<(), KZGError> {
+        if self.gs.len() < 2 || self.hs.len() < 2 {
+            return Err(KZGError::InvalidParams(
+                "at least two G1 and two G2 powers are required".to_string(),
+            ));
+        }
+
+        let gs = E::G1Projective::batch_normalization_into_affine(&self.gs);
+        for (i, g) in gs.iter().enumerate() {
+            if !is_valid_point(g) {
+                return Err(KZGError::InvalidParams(format!(
+                    "gs[{}] is not in the prime-order subgroup",
+                    i
+                )));
+            }
+        }
+
+        let hs = E::G2Projective::batch_normalization_into_affine(&self.hs);
+        for (i, h) in hs.iter().enumerate() {
+            if !is_valid_point(h) {
+                return Err(KZGError::InvalidParams(format!(
+                    "hs[{}] is not in the prime-order subgroup",
+                    i
+                )));
+            }
+        }
+
+        if gs[0] != E::G1Affine::prime_subgroup_generator() {
+            return Err(KZGError::InvalidParams(
+                "gs[0] is not the G1 generator".to_string(),
+            ));
+        }
+        if hs[0] != E::G2Affine::prime_subgroup_generator() {
+            return Err(KZGError::InvalidParams(
+                "hs[0] is not the G2 generator".to_string(),
+            ));
+        }
+
+        let mut rng = rand::thread_rng();
+
+        let rs = (1..gs.len())
+            .map(|_| E::Fr::rand(&mut rng))
+            .collect::<Vec<E::Fr>>();
+        let g1_consistent = pairing_product_is_one::<E>(&[
+            (msm(&self.gs[1..], &rs), self.hs[0]),
+            (-msm(&self.gs, &rs), self.hs[1]),
+        ]);
+        if !g1_consistent {
+            for i in 0..gs.len() - 1 {
+                if !pairing_product_is_one::<E>(&[
+                    (self.gs[i + 1], self.hs[0]),
+                    (-self.gs[i], self.hs[1]),
+                ]) {
+                    return Err(KZGError::InvalidParams(format!(
+                        "e(gs[{}], hs[0]) != e(gs[{}], hs[1])",
+                        i + 1,
+                        i
+                    )));
+                }
+            }
+        }
+
+        let rs = (1..hs.len())
+            .map(|_| E::Fr::rand(&mut rng))
+            .collect::<Vec<E::Fr>>();
+        let g2_consistent = pairing_product_is_one::<E>(&[
+            (self.gs[0], msm(&self.hs[1..], &rs)),
+            (-self.gs[1], msm(&self.hs, &rs)),
+        ]);
+        if !g2_consistent {
+            for i in 0..hs.len() - 1 {
+                if !pairing_product_is_one::<E>(&[
+                    (self.gs[0], self.hs[i + 1]),
+                    (-self.gs[1], self.hs[i]),
+                ]) {
+                    return Err(KZGError::InvalidParams(format!(
+                        "e(gs[0], hs[{}]) != e(gs[1], hs[{}])",
+                        i + 1,
+                        i
+                    )));
+                }
+            }
+        }
+
+        if let Some(lagrange_gs) = &self.lagrange_gs {
+            let domain = self
+                .lagrange_domain()
+                .filter(|domain| domain.size() == lagrange_gs.len())
+                .ok_or_else(|| {
+                    KZGError::InvalidParams("lagrange_gs length is not a power of two".to_string())
+                })?;
+            if domain.size() > self.gs.len() {
+                return Err(KZGError::InvalidParams(
+                    "lagrange_gs is longer than gs".to_string(),
+                ));
+            }
+
+            // a random polynomial must get the same commitment in both bases
+            let coeffs = (0..domain.size())
+                .map(|_| E::Fr::rand(&mut rng))
+                .collect::<Vec<E::Fr>>();
+            if msm(&self.gs, &coeffs) != msm(lagrange_gs, &domain.fft(&coeffs)?) {
+                return Err(KZGError::InvalidParams(
+                    "lagrange_gs is not the lagrange basis of gs".to_string(),
+                ));
+            }
+        }
+
+        if let Some(blinding_gs) = &self.blinding_gs {
+            let blinding_affine = E::G1Projective::batch_normalization_into_affine(blinding_gs);
+            for (i, b) in blinding_affine.iter().enumerate() {
+                if !is_valid_point(b) {
+                    return Err(KZGError::InvalidParams(format!(
+                        "blinding_gs[{}] is not in the prime-order subgroup",
+                        i
+                    )));
+                }
+            }
+            if blinding_gs.len() < 2 || blinding_affine[0] == gs[0] {
+                return Err(KZGError::InvalidParams(
+                    "blinding_gs needs at least two powers of a generator other than g".to_string(),
+                ));
+            }
+
+            // e(b[i+1], hs[0]) == e(b[i], hs[1]), batched like the checks on gs
+            let rs = (1..blinding_gs.len())
+                .map(|_| E::Fr::rand(&mut rng))
+                .collect::<Vec<E::Fr>>();
+            if !pairing_product_is_one::<E>(&[
+                (msm(&blinding_gs[1..], &rs), self.hs[0]),
+                (-msm(blinding_gs, &rs), self.hs[1]),
+            ]) {
+                return Err(KZGError::InvalidParams(
+                    "blinding_gs are not powers of the same secret as gs".to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone)]
-pub struct KZGVerifier<'params> {
-    parameters: &'params KZGParams,
+pub struct KZGVerifier<'params, E: PairingEngine = Bn254> {
+    parameters: &'params KZGParams<E>,
+    /// [1]_2 and [s]_2, prepared once for the miller loops of every check
+    h_prepared: E::G2Prepared,
+    hs_prepared: E::G2Prepared,
 }
 
-impl<'params> KZGProver<'params> {
+impl<'params, E: PairingEngine> KZGProver<'params, E> {
     /// initializes `polynomial` to zero polynomial
-    pub fn new(parameters: &'params KZGParams) -> Self {
+    pub fn new(parameters: &'params KZGParams<E>) -> Self {
         Self { parameters }
     }
 
-    pub fn parameters(&self) -> &'params KZGParams {
+    pub fn parameters(&self) -> &'params KZGParams<E> {
         self.parameters
     }
 
-    pub fn commit(&self, polynomial: &Polynomial) -> KZGCommitment {
-        let gs = &self.parameters.gs[..polynomial.num_coeffs()]
-            .iter()
-            .map(|e| G1Affine::from(*e))
-            .collect::<Vec<G1Affine>>();
-        let coeffs = &polynomial
-            .slice_coeffs()
+    /// fails if the polynomial has more coefficients than the parameters have powers
+    pub fn commit(&self, polynomial: &Polynomial<E::Fr>) -> Result<KZGCommitment<E>, KZGError> {
+        check_srs_size(polynomial.num_coeffs(), self.parameters.gs.len())?;
+        Ok(msm(&self.parameters.gs, polynomial.slice_coeffs()).into())
+    }
+
+    pub fn create_witness(
+        &self,
+        polynomial: &Polynomial<E::Fr>,
+        (x, y): (E::Fr, E::Fr),
+    ) -> Result<KZGWitness<E>, KZGError> {
+        let divisor = Polynomial::new_from_coeffs(vec![-x, E::Fr::one()], 1);
+        let (quotient, remainder) = (polynomial - &Polynomial::from_scalar(y))
+            .div_rem(&divisor)
+            .ok_or(KZGError::NoPolynomial)?;
+        if !remainder.is_zero() {
+            return Err(KZGError::PointNotOnPolynomial);
+        }
+
+        self.commit(&quotient)
+    }
+
+    /// single witness for the evaluations of `polynomial` at every point in `points`: a commitment
+    /// to p(X) / Z(X), where Z vanishes on the points. the remainder p mod Z interpolates the points.
+    pub fn create_batch_witness(
+        &self,
+        polynomial: &Polynomial<E::Fr>,
+        points: &[(E::Fr, E::Fr)],
+    ) -> Result<KZGWitness<E>, KZGError> {
+        if points.is_empty() {
+            return self.commit(polynomial);
+        }
+
+        let (xs, ys): (Vec<E::Fr>, Vec<E::Fr>) = points.iter().copied().unzip();
+        polynomial::check_interpolation_points(&xs, &ys)?;
+        let tree = SubproductTree::new(&xs)?;
+
+        let (quotient, remainder) = polynomial
+            .div_rem(tree.vanishing_polynomial())
+            .ok_or(KZGError::NoPolynomial)?;
+        if tree.evaluate(&remainder) != ys {
+            return Err(KZGError::PointNotOnPolynomial);
+        }
+
+        self.commit(&quotient)
+    }
+
+    /// commitment to the polynomial taking the values `evals` at 1, w, w^2, ... of the parameters'
+    /// lagrange domain, with missing values taken as zero. gives the same commitment as
+    /// `commit(&Polynomial::from_evaluations_over_domain(evals, &domain))` without interpolating.
+    pub fn commit_evaluations(&self, evals: &[E::Fr]) -> Result<KZGCommitment<E>, KZGError> {
+        let (lagrange_gs, _) = self.lagrange_basis(evals)?;
+        Ok(msm(lagrange_gs, evals).into())
+    }
+
+    /// `create_witness` for the polynomial taking the values `evals` over the lagrange domain, as
+    /// committed to by `commit_evaluations`. returns the witness for z along with y = p(z).
+    ///
+    /// the quotient q(X) = (p(X) - y) / (X - z) is computed in evaluation form, q(w^i) =
+    /// (evals[i] - y) / (w^i - z), so coefficients never come into it. if z = w^m is itself a
+    /// domain point that formula is 0/0 at i = m, and instead
+    ///     q(w^m) = sum_{i != m} (evals[i] - y) * w^i / (z * (z - w^i))
+    /// the witness verifies with `KZGVerifier::verify_eval` like any other.
+    pub fn create_witness_from_evaluations(
+        &self,
+        evals: &[E::Fr],
+        z: E::Fr,
+    ) -> Result<(KZGWitness<E>, E::Fr), KZGError> {
+        let (lagrange_gs, domain) = self.lagrange_basis(evals)?;
+        let y = domain.evaluate_from_evaluations(evals, z)?;
+
+        let roots = domain.elements();
+        let mut denominators: Vec<E::Fr> = roots.iter().map(|w| *w - z).collect();
+        let z_index = denominators.iter().position(Zero::is_zero);
+        if let Some(m) = z_index {
+            denominators[m] = E::Fr::one();
+        }
+        batch_inversion(&mut denominators);
+
+        let mut quotient: Vec<E::Fr> = denominators
             .iter()
-            .map(|c| c.into_repr())
-            .collect::<Vec<BigInteger256>>();
-        let commitment = VariableBaseMSM::multi_scalar_mul(gs, &coeffs);
+            .enumerate()
+            .map(|(i, d)| (evals.get(i).copied().unwrap_or_else(E::Fr::zero) - y) * d)
+            .collect();
 
-        commitment.into()
+        if let Some(m) = z_index {
+            // (evals[i] - y) * w^i / (z * (z - w^i)) = -q(w^i) * w^i / z
+            let z_inv = z.inverse().unwrap();
+            quotient[m] = -quotient
+                .iter()
+                .zip(roots.iter())
+                .enumerate()
+                .filter(|(i, _)| *i != m)
+                .map(|(_, (q, w))| *q * w)
+                .sum::<E::Fr>()
+                * z_inv;
+        }
+
+        Ok((msm(lagrange_gs, &quotient).into(), y))
     }
 
-    pub fn create_witness(
+    /// the lagrange basis and its domain, if `evals` fits in it
+    fn lagrange_basis(&self, evals: &[E::Fr]) -> Result<LagrangeBasis<'params, E>, KZGError> {
+        let lagrange_gs = self
+            .parameters
+            .lagrange_gs
+            .as_ref()
+            .ok_or(KZGError::NoLagrangeBasis)?;
+        if evals.len() > lagrange_gs.len() {
+            return Err(KZGError::PolynomialDegreeTooLarge);
+        }
+
+        let domain = self
+            .parameters
+            .lagrange_domain()
+            .ok_or(KZGError::NoLagrangeBasis)?;
+        Ok((lagrange_gs, domain))
+    }
+
+    /// witnesses for every point of `domain` at once with O(n log n) group operations, rather
+    /// than an O(n) division and MSM per point with `create_witness`
+    pub fn open_all_on_domain(
         &self,
-        polynomial: &Polynomial,
-        (x, y): (Scalar, Scalar),
-    ) -> Result<KZGWitness, KZGError> {
-        let mut dividend = polynomial.clone();
-        let degree = dividend.degree();
-        dividend.coeffs[0] -= y;
+        polynomial: &Polynomial<E::Fr>,
+        domain: &EvaluationDomain<E::Fr>,
+    ) -> Result<OpeningCache<E>, KZGError> {
+        // the quotients have `degree` coefficients
+        if polynomial.degree() > self.parameters.gs.len() {
+            return Err(KZGError::PolynomialDegreeTooLarge);
+        }
 
-        let mut remainder = polynomial.clone();
-        let mut divpoly = Polynomial::new_from_coeffs(vec![Scalar::zero(); degree], degree - 1);
+        Ok(OpeningCache {
+            domain: *domain,
+            evaluations: polynomial.evaluate_over_domain(domain),
+            witnesses: fk20::all_witnesses::<E>(&self.parameters.gs, polynomial, domain),
+        })
+    }
 
-        for i in (1..=degree).rev() {
-            let factor = remainder.coeffs[i];
-            divpoly.coeffs[i - 1] = factor;
-            remainder.coeffs[i - 1] += x * factor;
+    /// proof that `polynomial` has degree at most `degree_bound`: a commitment to
+    /// X^(D - d) p(X), where D + 1 is the number of powers in the parameters, which only exists if
+    /// deg p <= d. check it with `KZGVerifier::verify_degree_bound`, which needs [s^(D - d)]_2, so
+    /// this fails for bounds the parameters' G2 powers can't check.
+    pub fn prove_degree_bound(
+        &self,
+        polynomial: &Polynomial<E::Fr>,
+        degree_bound: usize,
+    ) -> Result<KZGCommitment<E>, KZGError> {
+        let max_degree = self
+            .parameters
+            .gs
+            .len()
+            .checked_sub(1)
+            .ok_or(KZGError::SrsTooSmall(1, 0))?;
+        if degree_bound > max_degree {
+            return Err(KZGError::InvalidInput(format!(
+                "degree bound {} is more than the parameters' maximum degree {}",
+                degree_bound, max_degree
+            )));
+        }
+        if self.parameters.hs.len() <= max_degree - degree_bound {
+            return Err(KZGError::InvalidInput(format!(
+                "degree bound {} needs G2 power {} but the parameters only have {}",
+                degree_bound,
+                max_degree - degree_bound,
+                self.parameters.hs.len()
+            )));
+        }
+        let coeffs = polynomial.slice_coeffs();
+        if Polynomial::compute_degree(coeffs, coeffs.len() - 1) > degree_bound {
+            return Err(KZGError::PolynomialDegreeTooLarge);
         }
 
-        if divpoly.num_coeffs() == 1 {
-            Ok((self.parameters.gs[0].mul(&divpoly.coeffs[0].into_repr())).into())
-        } else {
-            Ok(self.commit(&divpoly))
+        let coeffs = &coeffs[..coeffs.len().min(degree_bound + 1)];
+        let shift = max_degree - degree_bound;
+        Ok(msm(&self.parameters.gs[shift..], coeffs).into())
+    }
+
+    /// hiding commitment to `polynomial`: sum a_i g_i + sum b_i h_i for a random blinding
+    /// polynomial b with as many coefficients, so equal polynomials get unrelated commitments.
+    /// returns b too, which is needed for openings and must otherwise stay secret.
+    pub fn commit_hiding<R: Rng>(
+        &self,
+        polynomial: &Polynomial<E::Fr>,
+        rng: &mut R,
+    ) -> Result<(KZGCommitment<E>, Polynomial<E::Fr>), KZGError> {
+        let blinding_gs = self.blinding_powers(polynomial)?;
+        let blinding = Polynomial::new_nonempty(
+            (0..polynomial.num_coeffs().max(2))
+                .map(|_| E::Fr::rand(rng))
+                .collect(),
+        );
+
+        let commitment = msm(&self.parameters.gs, polynomial.slice_coeffs())
+            + msm(blinding_gs, blinding.slice_coeffs());
+        Ok((commitment.into(), blinding))
+    }
+
+    /// witness for a commitment from `commit_hiding` being y at x: commitments to the quotients
+    /// of both `polynomial` and `blinding` by (X - x). returns it along with b(x), which the
+    /// verifier needs in place of the blinding polynomial.
+    pub fn create_hiding_witness(
+        &self,
+        polynomial: &Polynomial<E::Fr>,
+        blinding: &Polynomial<E::Fr>,
+        (x, y): (E::Fr, E::Fr),
+    ) -> Result<(KZGWitness<E>, E::Fr), KZGError> {
+        let blinding_gs = self.blinding_powers(blinding)?;
+        check_srs_size(polynomial.num_coeffs(), self.parameters.gs.len())?;
+        let divisor = Polynomial::new_from_coeffs(vec![-x, E::Fr::one()], 1);
+
+        let (quotient, remainder) = (polynomial - &Polynomial::from_scalar(y))
+            .div_rem(&divisor)
+            .ok_or(KZGError::NoPolynomial)?;
+        if !remainder.is_zero() {
+            return Err(KZGError::PointNotOnPolynomial);
         }
+        let blinding_eval = blinding.eval(x);
+        let (blinding_quotient, _) = (blinding - &Polynomial::from_scalar(blinding_eval))
+            .div_rem(&divisor)
+            .ok_or(KZGError::NoPolynomial)?;
+
+        let witness = msm(&self.parameters.gs, quotient.slice_coeffs())
+            + msm(blinding_gs, blinding_quotient.slice_coeffs());
+        Ok((witness.into(), blinding_eval))
+    }
+
+    /// the blinding powers, if there are enough for `polynomial`
+    fn blinding_powers(
+        &self,
+        polynomial: &Polynomial<E::Fr>,
+    ) -> Result<&'params [E::G1Projective], KZGError> {
+        let blinding_gs = self
+            .parameters
+            .blinding_gs
+            .as_ref()
+            .ok_or(KZGError::NoBlindingPowers)?;
+        if polynomial.num_coeffs().max(2) > blinding_gs.len()
+            || polynomial.num_coeffs() > self.parameters.gs.len()
+        {
+            return Err(KZGError::PolynomialDegreeTooLarge);
+        }
+        Ok(blinding_gs)
+    }
+
+    /// one witness for the evaluations of every polynomial at the same point x: a witness for
+    /// sum gamma^i p_i at x. returns it along with the evaluations p_i(x). gamma should be
+    /// squeezed from a transcript after absorbing the commitments, x and the evaluations, so the
+    /// prover can't choose it.
+    pub fn create_multi_poly_witness(
+        &self,
+        polynomials: &[&Polynomial<E::Fr>],
+        x: E::Fr,
+        gamma: E::Fr,
+    ) -> Result<(KZGWitness<E>, Vec<E::Fr>), KZGError> {
+        if polynomials.is_empty() {
+            return Err(KZGError::NoPolynomial);
+        }
+        let num_coeffs = polynomials.iter().map(|p| p.num_coeffs()).max().unwrap();
+        if num_coeffs > self.parameters.gs.len() {
+            return Err(KZGError::PolynomialDegreeTooLarge);
+        }
+
+        let ys = polynomials.iter().map(|p| p.eval(x)).collect::<Vec<_>>();
+        let mut combined = vec![E::Fr::zero(); num_coeffs];
+        let mut gamma_i = E::Fr::one();
+        for polynomial in polynomials {
+            for (c, coeff) in combined.iter_mut().zip(polynomial.iter_coeffs()) {
+                *c += gamma_i * coeff;
+            }
+            gamma_i *= gamma;
+        }
+
+        let combined = Polynomial::new_nonempty(combined);
+        let y = combine(&ys, gamma);
+        Ok((self.create_witness(&combined, (x, y))?, ys))
+    }
+
+    /// non-interactive `create_witness`: absorbs `commitment`, squeezes the point x from
+    /// `transcript` and absorbs y = p(x), so the verifier never has to pick x. check the result
+    /// with `KZGVerifier::verify_at_challenge` on a transcript in the same state.
+    pub fn prove_at_challenge<H: TranscriptHash>(
+        &self,
+        polynomial: &Polynomial<E::Fr>,
+        commitment: &KZGCommitment<E>,
+        transcript: &mut Transcript<H>,
+    ) -> Result<Opening<E>, KZGError>
+    where
+        E::G1Affine: TranscriptPoint,
+    {
+        transcript.append_point(b"commitment", commitment);
+        let x = transcript.challenge_scalar(b"x");
+        let y = polynomial.eval(x);
+        transcript.append_scalar(b"y", &y);
+
+        Ok(((x, y), self.create_witness(polynomial, (x, y))?))
     }
 }
 
-impl<'params> KZGVerifier<'params> {
-    pub fn new(parameters: &'params KZGParams) -> Self {
-        KZGVerifier { parameters }
+impl<'params, E: PairingEngine> KZGVerifier<'params, E> {
+    /// fails if the parameters don't have the G2 powers [1]_2 and [s]_2 that every check needs
+    pub fn new(parameters: &'params KZGParams<E>) -> Result<Self, KZGError> {
+        if parameters.hs.len() < 2 {
+            return Err(KZGError::InvalidParams(
+                "at least two G2 powers are required".to_string(),
+            ));
+        }
+
+        Ok(KZGVerifier {
+            parameters,
+            h_prepared: E::G2Affine::from(parameters.hs[0]).into(),
+            hs_prepared: E::G2Affine::from(parameters.hs[1]).into(),
+        })
+    }
+
+    /// whether `commitment` is to `polynomial`. fails if the polynomial has more coefficients
+    /// than the parameters have powers.
+    pub fn verify_poly(
+        &self,
+        commitment: &KZGCommitment<E>,
+        polynomial: &Polynomial<E::Fr>,
+    ) -> Result<bool, KZGError> {
+        check_srs_size(polynomial.num_coeffs(), self.parameters.gs.len())?;
+        let check = msm(&self.parameters.gs, polynomial.slice_coeffs());
+
+        Ok(E::G1Affine::from(check) == *commitment)
+    }
+
+    /// checks e(W, h^(s - x)) == e(C - y g, h), rearranged so that G2 is only ever paired with
+    /// the prepared [s]_2 and [1]_2:
+    ///     e(W, [s]_2) * e(-(C - y g + x W), [1]_2) == 1
+    pub fn verify_eval(
+        &self,
+        (x, y): (E::Fr, E::Fr),
+        commitment: &KZGCommitment<E>,
+        witness: &KZGWitness<E>,
+    ) -> bool {
+        let rhs = commitment.into_projective() - self.parameters.gs[0].mul(y.into_repr())
+            + witness.mul(x.into_repr());
+
+        self.check_with_prepared(witness.into_projective(), rhs)
+    }
+
+    /// checks a proof from `KZGProver::prove_degree_bound` that the committed polynomial has
+    /// degree at most `degree_bound`, i.e. that e(C, [s^(D - d)]_2) == e(proof, [1]_2). false if
+    /// the parameters don't have that G2 power.
+    pub fn verify_degree_bound(
+        &self,
+        commitment: &KZGCommitment<E>,
+        degree_bound: usize,
+        proof: &KZGCommitment<E>,
+    ) -> bool {
+        let shifted_h = match self
+            .parameters
+            .gs
+            .len()
+            .checked_sub(1)
+            .and_then(|max_degree| max_degree.checked_sub(degree_bound))
+            .and_then(|shift| self.parameters.hs.get(shift))
+        {
+            Some(h) => E::G2Affine::from(*h),
+            None => return false,
+        };
+
+        E::product_of_pairings(&[
+            ((*commitment).into(), shifted_h.into()),
+            ((-*proof).into(), self.h_prepared.clone()),
+        ])
+        .is_one()
+    }
+
+    /// checks a witness from `KZGProver::create_hiding_witness`, i.e. that
+    ///     e(W, h^(s - x)) == e(C - y g - b(x) h, h)
+    /// which reveals y and b(x) but nothing more about the polynomial. false if the parameters
+    /// have no blinding powers.
+    pub fn verify_hiding_eval(
+        &self,
+        (x, y): (E::Fr, E::Fr),
+        blinding_eval: E::Fr,
+        commitment: &KZGCommitment<E>,
+        witness: &KZGWitness<E>,
+    ) -> bool {
+        let blinding_g = match &self.parameters.blinding_gs {
+            Some(blinding_gs) => blinding_gs[0],
+            None => return false,
+        };
+        let rhs = commitment.into_projective()
+            - self.parameters.gs[0].mul(y.into_repr())
+            - blinding_g.mul(blinding_eval.into_repr())
+            + witness.mul(x.into_repr());
+
+        self.check_with_prepared(witness.into_projective(), rhs)
     }
 
-    pub fn verify_poly(&self, commitment: &KZGCommitment, polynomial: &Polynomial) -> bool {
-        let gs = &self.parameters.gs[..polynomial.num_coeffs()]
+    /// checks a witness from `KZGProver::create_multi_poly_witness` that each committed
+    /// polynomial takes `ys[i]` at x, as a single `verify_eval` of sum gamma^i C_i at x
+    pub fn verify_multi_poly_eval(
+        &self,
+        commitments: &[KZGCommitment<E>],
+        x: E::Fr,
+        ys: &[E::Fr],
+        witness: &KZGWitness<E>,
+        gamma: E::Fr,
+    ) -> bool {
+        if commitments.is_empty() || commitments.len() != ys.len() {
+            return false;
+        }
+
+        let gammas = std::iter::successors(Some(E::Fr::one()), |g| Some(*g * gamma))
+            .take(commitments.len())
+            .map(|g| g.into_repr())
+            .collect::<Vec<_>>();
+        let commitment = VariableBaseMSM::multi_scalar_mul(commitments, &gammas);
+
+        self.verify_eval((x, combine(ys, gamma)), &commitment.into(), witness)
+    }
+
+    /// checks many independent `verify_eval` claims at once. each claim is
+    ///     e(W_i, h^s) == e(C_i - y_i g + x_i W_i, h)
+    /// and with random r_i they combine into a single check with two miller loops,
+    ///     e(sum r_i W_i, h^s) * e(-sum r_i (C_i - y_i g + x_i W_i), h) == 1
+    /// which fails except with negligible probability if any claim is bad.
+    pub fn verify_eval_batch(&self, claims: &[EvalClaim<E>]) -> bool {
+        if claims.is_empty() {
+            return true;
+        }
+
+        let mut rng = rand::thread_rng();
+        let rs = claims
             .iter()
-            .map(|e| G1Affine::from(*e))
-            .collect::<Vec<G1Affine>>();
-        let coeffs = &polynomial
-            .slice_coeffs()
+            .map(|_| E::Fr::rand(&mut rng))
+            .collect::<Vec<E::Fr>>();
+
+        let commitments = claims.iter().map(|claim| claim.2).collect::<Vec<_>>();
+        let witnesses = claims.iter().map(|claim| claim.3).collect::<Vec<_>>();
+        let rxs = claims
             .iter()
-            .map(|c| c.into_repr())
-            .collect::<Vec<BigInteger256>>();
-        let check = VariableBaseMSM::multi_scalar_mul(gs, &coeffs);
+            .zip(rs.iter())
+            .map(|((x, ..), r)| (*x * r).into_repr())
+            .collect::<Vec<_>>();
+        let ry = claims
+            .iter()
+            .zip(rs.iter())
+            .map(|((_, y, ..), r)| *y * r)
+            .sum::<E::Fr>();
+        let rs = rs.iter().map(|r| r.into_repr()).collect::<Vec<_>>();
+
+        let witness_sum = VariableBaseMSM::multi_scalar_mul(&witnesses, &rs);
+        let rhs = VariableBaseMSM::multi_scalar_mul(&commitments, &rs)
+            + VariableBaseMSM::multi_scalar_mul(&witnesses, &rxs)
+            - self.parameters.gs[0].mul(ry.into_repr());
 
-        G1Affine::from(check) == *commitment
+        self.check_with_prepared(witness_sum, rhs)
     }
 
-    pub fn verify_eval(
+    /// e(lhs, [s]_2) * e(-rhs, [1]_2) == 1, with two miller loops and one final exponentiation
+    fn check_with_prepared(&self, lhs: E::G1Projective, rhs: E::G1Projective) -> bool {
+        E::product_of_pairings(&[
+            (E::G1Affine::from(lhs).into(), self.hs_prepared.clone()),
+            (E::G1Affine::from(-rhs).into(), self.h_prepared.clone()),
+        ])
+        .is_one()
+    }
+
+    /// checks a witness from `KZGProver::create_batch_witness`, i.e. that
+    /// e(W, [Z(s)]_2) == e(C - [I(s)]_1, h) where I interpolates `points` and Z vanishes on them
+    pub fn verify_batch_eval(
         &self,
-        (x, y): (Scalar, Scalar),
-        commitment: &KZGCommitment,
-        witness: &KZGWitness,
+        points: &[(E::Fr, E::Fr)],
+        commitment: &KZGCommitment<E>,
+        witness: &KZGWitness<E>,
     ) -> bool {
-        let lhs = Bn254::pairing::<G1Affine, G2Affine>(
-            *witness,
-            (self.parameters.hs[1] - self.parameters.hs[0].mul(&x.into_repr())).into(),
-        );
-        let rhs = Bn254::pairing::<G1Affine, G2Affine>(
-            (Into::<G1Projective>::into(*commitment) - self.parameters.gs[0].mul(&y.into_repr()))
-                .into(),
-            self.parameters.hs[0].into(),
-        );
+        let (interpolation, vanishing) = if points.is_empty() {
+            (
+                Polynomial::new_zero(),
+                Polynomial::from_scalar(E::Fr::one()),
+            )
+        } else {
+            let (xs, ys): (Vec<E::Fr>, Vec<E::Fr>) = points.iter().copied().unzip();
+            // duplicate points can't be interpolated
+            let interpolated = SubproductTree::new(&xs)
+                .and_then(|tree| Ok((tree.interpolate(&ys)?, tree.vanishing_polynomial().clone())));
+            match interpolated {
+                Ok(interpolated) => interpolated,
+                Err(_) => return false,
+            }
+        };
 
-        lhs == rhs
+        if interpolation.num_coeffs() > self.parameters.gs.len()
+            || vanishing.num_coeffs() > self.parameters.hs.len()
+        {
+            return false;
+        }
+
+        let interpolation_commitment = msm(&self.parameters.gs, interpolation.slice_coeffs());
+        let vanishing_commitment = msm(&self.parameters.hs, vanishing.slice_coeffs());
+        let lhs = Into::<E::G1Projective>::into(*commitment) - interpolation_commitment;
+
+        E::product_of_pairings(&[
+            (
+                (*witness).into(),
+                E::G2Affine::from(vanishing_commitment).into(),
+            ),
+            (E::G1Affine::from(-lhs).into(), self.h_prepared.clone()),
+        ])
+        .is_one()
+    }
+
+    /// checks an opening from `KZGProver::prove_at_challenge`, re-deriving x from `transcript`
+    pub fn verify_at_challenge<H: TranscriptHash>(
+        &self,
+        commitment: &KZGCommitment<E>,
+        y: E::Fr,
+        witness: &KZGWitness<E>,
+        transcript: &mut Transcript<H>,
+    ) -> bool
+    where
+        E::G1Affine: TranscriptPoint,
+    {
+        transcript.append_point(b"commitment", commitment);
+        let x = transcript.challenge_scalar(b"x");
+        transcript.append_scalar(b"y", &y);
+
+        self.verify_eval((x, y), commitment, witness)
+    }
+}
+
+/// `needed` powers out of `available`
+fn check_srs_size(needed: usize, available: usize) -> Result<(), KZGError> {
+    if needed > available {
+        return Err(KZGError::SrsTooSmall(needed, available));
     }
+    Ok(())
+}
+
+/// sum gamma^i values[i]
+fn combine<F: PrimeField>(values: &[F], gamma: F) -> F {
+    values
+        .iter()
+        .rev()
+        .fold(F::zero(), |acc, value| acc * gamma + value)
+}
+
+/// prod e(a_i, b_i) == 1, with a single final exponentiation
+fn pairing_product_is_one<E: PairingEngine>(pairs: &[(E::G1Projective, E::G2Projective)]) -> bool {
+    let prepared = pairs
+        .iter()
+        .map(|(a, b)| (E::G1Affine::from(*a).into(), E::G2Affine::from(*b).into()))
+        .collect::<Vec<_>>();
+
+    E::product_of_pairings(&prepared).is_one()
+}
+
+/// non-identity, on the curve and in the prime-order subgroup. `AffineCurve` has no methods for
+/// the last two, but uncompressed deserialization checks both.
+fn is_valid_point<G: AffineCurve>(point: &G) -> bool {
+    let mut bytes = Vec::with_capacity(point.uncompressed_size());
+    !point.is_zero()
+        && point.serialize_uncompressed(&mut bytes).is_ok()
+        && G::deserialize_uncompressed(bytes.as_slice()).is_ok()
+}
+
+/// sum coeffs[i] * bases[i]
+fn msm<G: ProjectiveCurve>(bases: &[G], coeffs: &[G::ScalarField]) -> G {
+    let bases = G::batch_normalization_into_affine(&bases[..coeffs.len()]);
+    let coeffs = coeffs.iter().map(|c| c.into_repr()).collect::<Vec<_>>();
+
+    VariableBaseMSM::multi_scalar_mul(&bases, &coeffs)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::kzg::setup;
+    use ark_bn254::{G1Affine, G2Projective};
     use rand::{rngs::SmallRng, Rng, SeedableRng};
 
     const RNG_SEED: [u8; 32] = [69; 32];
@@ -175,7 +945,7 @@ mod tests {
 
     fn test_participants(params: &'_ KZGParams) -> (KZGProver<'_>, KZGVerifier<'_>) {
         let prover = KZGProver::new(params);
-        let verifier = KZGVerifier::new(params);
+        let verifier = KZGVerifier::new(params).unwrap();
 
         (prover, verifier)
     }
@@ -200,7 +970,7 @@ mod tests {
         polynomial: &Polynomial,
     ) {
         assert!(
-            verifier.verify_poly(commitment, polynomial),
+            verifier.verify_poly(commitment, polynomial).unwrap(),
             "verify_poly failed for commitment {:#?} and polynomial {:#?}",
             commitment,
             polynomial
@@ -213,7 +983,7 @@ mod tests {
         polynomial: &Polynomial,
     ) {
         assert!(
-            !verifier.verify_poly(commitment, polynomial),
+            !verifier.verify_poly(commitment, polynomial).unwrap(),
             "expected verify_poly to fail for commitment {:#?} and polynomial {:#?} but it didn't",
             commitment,
             polynomial
@@ -252,7 +1022,7 @@ mod tests {
         let (prover, verifier) = test_participants(&params);
 
         let polynomial = random_polynomial(&mut rng, 2, 12);
-        let commitment = prover.commit(&polynomial);
+        let commitment = prover.commit(&polynomial).unwrap();
 
         assert_verify_poly(&verifier, &commitment, &polynomial);
         assert_verify_poly_fails(&verifier, &commitment, &random_polynomial(&mut rng, 2, 12));
@@ -276,7 +1046,7 @@ mod tests {
         let (prover, verifier) = test_participants(&params);
 
         let polynomial = random_polynomial(&mut rng, 3, 8);
-        let commitment = prover.commit(&polynomial);
+        let commitment = prover.commit(&polynomial).unwrap();
 
         let mut modified_polynomial = polynomial.clone();
         let new_coeff = random_field_elem_neq(modified_polynomial.coeffs[2]);
@@ -294,7 +1064,7 @@ mod tests {
         let (prover, verifier) = test_participants(&params);
 
         let polynomial = random_polynomial(&mut rng, 5, 13);
-        let commitment = prover.commit(&polynomial);
+        let commitment = prover.commit(&polynomial).unwrap();
 
         let x: Scalar = rng.gen::<u64>().into();
         let y = polynomial.eval(x);
@@ -309,13 +1079,555 @@ mod tests {
         let mut coeffs = vec![Scalar::zero(); 13];
         coeffs[0] = 3.into();
         coeffs[1] = 1.into();
-        let polynomial = Polynomial::new(coeffs);
+        let polynomial = Polynomial::new(coeffs).unwrap();
 
-        let commitment = prover.commit(&polynomial);
+        let commitment = prover.commit(&polynomial).unwrap();
         let witness = prover
             .create_witness(&polynomial, (1.into(), 4.into()))
             .unwrap();
         assert_verify_eval(&verifier, (1.into(), 4.into()), &commitment, &witness);
         assert_verify_eval_fails(&verifier, (1.into(), 5.into()), &commitment, &witness);
     }
+
+    #[test]
+    fn test_commit_evaluations() {
+        let mut rng = SmallRng::from_seed(RNG_SEED);
+        let domain = EvaluationDomain::new(8).unwrap();
+        let params = test_setup::<10>(&mut rng);
+
+        let prover = KZGProver::new(&params);
+        assert!(matches!(
+            prover.commit_evaluations(&[Scalar::one()]),
+            Err(KZGError::NoLagrangeBasis)
+        ));
+
+        let params = params.with_lagrange_basis(&domain).unwrap();
+        params.validate().unwrap();
+        assert_eq!(params.lagrange_domain(), Some(domain));
+
+        let (prover, verifier) = test_participants(&params);
+        for num_evals in [1, 5, 8] {
+            let evals: Vec<Scalar> = (0..num_evals).map(|_| rng.gen::<u64>().into()).collect();
+            let commitment = prover.commit_evaluations(&evals).unwrap();
+
+            let polynomial = Polynomial::from_evaluations_over_domain(&evals, &domain).unwrap();
+            assert_eq!(commitment, prover.commit(&polynomial).unwrap());
+            assert_verify_poly(&verifier, &commitment, &polynomial);
+        }
+
+        assert!(matches!(
+            prover.commit_evaluations(&[Scalar::one(); 9]),
+            Err(KZGError::PolynomialDegreeTooLarge)
+        ));
+
+        let mut bad_basis = params.clone();
+        bad_basis.lagrange_gs.as_mut().unwrap().swap(1, 2);
+        assert_invalid_params(&bad_basis, "lagrange_gs is not the lagrange basis of gs");
+
+        assert!(test_setup::<4>(&mut rng)
+            .with_lagrange_basis(&domain)
+            .is_err());
+    }
+
+    #[test]
+    fn test_witness_from_evaluations() {
+        let mut rng = SmallRng::from_seed(RNG_SEED);
+        let domain = EvaluationDomain::new(8).unwrap();
+        let params = test_setup::<8>(&mut rng)
+            .with_lagrange_basis(&domain)
+            .unwrap();
+        let (prover, verifier) = test_participants(&params);
+
+        for num_evals in [1, 6, 8] {
+            let evals: Vec<Scalar> = (0..num_evals).map(|_| Scalar::rand(&mut rng)).collect();
+            let polynomial = Polynomial::from_evaluations_over_domain(&evals, &domain).unwrap();
+            let commitment = prover.commit_evaluations(&evals).unwrap();
+
+            // outside the domain, at a domain point with a value, and at one past `evals`
+            for z in [Scalar::rand(&mut rng), domain.element(0), domain.element(7)] {
+                let (witness, y) = prover.create_witness_from_evaluations(&evals, z).unwrap();
+                assert_eq!(y, polynomial.eval(z));
+                assert_eq!(witness, prover.create_witness(&polynomial, (z, y)).unwrap());
+
+                assert_verify_eval(&verifier, (z, y), &commitment, &witness);
+                let y_prime = random_field_elem_neq(y);
+                assert_verify_eval_fails(&verifier, (z, y_prime), &commitment, &witness);
+            }
+        }
+
+        assert!(matches!(
+            prover.create_witness_from_evaluations(&[Scalar::one(); 9], Scalar::one()),
+            Err(KZGError::PolynomialDegreeTooLarge)
+        ));
+    }
+
+    #[test]
+    fn test_verify_eval_batch() {
+        let mut rng = SmallRng::from_seed(RNG_SEED);
+        let params = test_setup::<16>(&mut rng);
+        let (prover, verifier) = test_participants(&params);
+
+        let claims = (0..20)
+            .map(|_| {
+                let polynomial = random_polynomial(&mut rng, 2, 16);
+                let x: Scalar = rng.gen::<u64>().into();
+                let y = polynomial.eval(x);
+                let witness = prover.create_witness(&polynomial, (x, y)).unwrap();
+                (x, y, prover.commit(&polynomial).unwrap(), witness)
+            })
+            .collect::<Vec<EvalClaim>>();
+
+        assert!(verifier.verify_eval_batch(&claims));
+        assert!(verifier.verify_eval_batch(&claims[..1]));
+        assert!(verifier.verify_eval_batch(&[]));
+
+        let mut bad_y = claims.clone();
+        bad_y[7].1 = random_field_elem_neq(bad_y[7].1);
+        assert!(!verifier.verify_eval_batch(&bad_y));
+
+        let mut bad_x = claims.clone();
+        bad_x[19].0 = random_field_elem_neq(bad_x[19].0);
+        assert!(!verifier.verify_eval_batch(&bad_x));
+
+        // swapping witnesses keeps sum W_i, but both claims are now bad
+        let mut swapped = claims.clone();
+        swapped[0].3 = claims[1].3;
+        swapped[1].3 = claims[0].3;
+        assert!(!verifier.verify_eval_batch(&swapped));
+
+        let mut bad_commitment = claims;
+        bad_commitment[3].2 = G1Affine::prime_subgroup_generator();
+        assert!(!verifier.verify_eval_batch(&bad_commitment));
+    }
+
+    #[test]
+    fn test_errors_instead_of_panics() {
+        let mut rng = SmallRng::from_seed(RNG_SEED);
+        let params = test_setup::<8>(&mut rng);
+        let (prover, verifier) = test_participants(&params);
+
+        let too_big = random_polynomial(&mut rng, 12, 13);
+        assert!(matches!(
+            prover.commit(&too_big),
+            Err(KZGError::SrsTooSmall(12, 8))
+        ));
+        let commitment = prover
+            .commit(&Polynomial::from_scalar(Scalar::one()))
+            .unwrap();
+        assert!(matches!(
+            verifier.verify_poly(&commitment, &too_big),
+            Err(KZGError::SrsTooSmall(12, 8))
+        ));
+        assert!(matches!(
+            prover.create_witness(&too_big, (Scalar::one(), too_big.eval(Scalar::one()))),
+            Err(KZGError::SrsTooSmall(..))
+        ));
+
+        // degree 0 polynomials open like any other
+        let constant = Polynomial::from_scalar(Scalar::from(7u64));
+        let x: Scalar = rng.gen::<u64>().into();
+        let witness = prover.create_witness(&constant, (x, 7u64.into())).unwrap();
+        let commitment = prover.commit(&constant).unwrap();
+        assert_verify_eval(&verifier, (x, 7u64.into()), &commitment, &witness);
+        assert!(matches!(
+            prover.create_witness(&constant, (x, 8u64.into())),
+            Err(KZGError::PointNotOnPolynomial)
+        ));
+
+        let polynomial = random_polynomial(&mut rng, 2, 8);
+        let duplicated = [(x, polynomial.eval(x)), (x, polynomial.eval(x))];
+        assert!(matches!(
+            prover.create_batch_witness(&polynomial, &duplicated),
+            Err(KZGError::DuplicatePoints)
+        ));
+        let witness = prover
+            .create_batch_witness(&polynomial, &duplicated[..1])
+            .unwrap();
+        let commitment = prover.commit(&polynomial).unwrap();
+        assert!(!verifier.verify_batch_eval(&duplicated, &commitment, &witness));
+    }
+
+    #[test]
+    fn test_degree_bound() {
+        let mut rng = SmallRng::from_seed(RNG_SEED);
+        let params = test_setup::<16>(&mut rng);
+        let (prover, verifier) = test_participants(&params);
+
+        let polynomial = random_polynomial(&mut rng, 5, 6);
+        assert_eq!(polynomial.degree(), 4);
+        let commitment = prover.commit(&polynomial).unwrap();
+
+        for degree_bound in [4, 5, 9, 15] {
+            let proof = prover
+                .prove_degree_bound(&polynomial, degree_bound)
+                .unwrap();
+            assert!(verifier.verify_degree_bound(&commitment, degree_bound, &proof));
+        }
+
+        // a proof for one bound says nothing about another
+        let proof = prover.prove_degree_bound(&polynomial, 5).unwrap();
+        assert!(!verifier.verify_degree_bound(&commitment, 4, &proof));
+        assert!(!verifier.verify_degree_bound(&commitment, 6, &proof));
+        assert!(!verifier.verify_degree_bound(&commitment, 16, &proof));
+
+        // a higher degree commitment doesn't verify with a smaller polynomial's proof
+        let larger = random_polynomial(&mut rng, 12, 13);
+        assert!(!verifier.verify_degree_bound(&prover.commit(&larger).unwrap(), 5, &proof));
+
+        assert!(matches!(
+            prover.prove_degree_bound(&polynomial, 3),
+            Err(KZGError::PolynomialDegreeTooLarge)
+        ));
+        assert!(matches!(
+            prover.prove_degree_bound(&polynomial, 16),
+            Err(KZGError::InvalidInput(_))
+        ));
+
+        // trailing zero coefficients beyond the bound don't count
+        let mut coeffs = polynomial.slice_coeffs().to_vec();
+        coeffs.resize(20, Scalar::zero());
+        let padded = Polynomial::new_from_coeffs(coeffs, 19);
+        assert_eq!(
+            prover.prove_degree_bound(&padded, 4).unwrap(),
+            prover.prove_degree_bound(&polynomial, 4).unwrap()
+        );
+
+        // with only [1]_2 and [s]_2, like a ceremony loaded with two G2 powers, only bounds of
+        // D and D - 1 can be checked
+        let mut two_g2 = params.clone();
+        two_g2.hs.truncate(2);
+        let (prover, verifier) = test_participants(&two_g2);
+        let proof = prover.prove_degree_bound(&polynomial, 14).unwrap();
+        assert!(verifier.verify_degree_bound(&commitment, 14, &proof));
+        assert!(matches!(
+            prover.prove_degree_bound(&polynomial, 13),
+            Err(KZGError::InvalidInput(_))
+        ));
+
+        let empty = KZGParams {
+            gs: vec![],
+            ..params
+        };
+        assert!(matches!(
+            KZGProver::new(&empty).prove_degree_bound(&polynomial, 0),
+            Err(KZGError::SrsTooSmall(_, _))
+        ));
+        assert!(!KZGVerifier::new(&empty)
+            .unwrap()
+            .verify_degree_bound(&commitment, 0, &proof));
+    }
+
+    #[test]
+    fn test_hiding_commitments() {
+        let mut rng = SmallRng::from_seed(RNG_SEED);
+        let params =
+            test_setup::<16>(&mut rng).with_insecure_blinding_powers(rng.gen::<u64>().into());
+        params.validate().unwrap();
+        let (prover, verifier) = test_participants(&params);
+
+        let polynomial = random_polynomial(&mut rng, 2, 16);
+        let (commitment, blinding) = prover.commit_hiding(&polynomial, &mut rng).unwrap();
+        let (other_commitment, _) = prover.commit_hiding(&polynomial, &mut rng).unwrap();
+        assert_ne!(commitment, other_commitment);
+        assert_ne!(commitment, prover.commit(&polynomial).unwrap());
+
+        let x: Scalar = rng.gen::<u64>().into();
+        let y = polynomial.eval(x);
+        let (witness, blinding_eval) = prover
+            .create_hiding_witness(&polynomial, &blinding, (x, y))
+            .unwrap();
+        assert_eq!(blinding_eval, blinding.eval(x));
+        assert!(verifier.verify_hiding_eval((x, y), blinding_eval, &commitment, &witness));
+
+        let y_prime = random_field_elem_neq(y);
+        assert!(!verifier.verify_hiding_eval((x, y_prime), blinding_eval, &commitment, &witness));
+        let blinding_prime = random_field_elem_neq(blinding_eval);
+        assert!(!verifier.verify_hiding_eval((x, y), blinding_prime, &commitment, &witness));
+        assert!(!verifier.verify_hiding_eval((x, y), blinding_eval, &other_commitment, &witness));
+        assert_verify_eval_fails(&verifier, (x, y), &commitment, &witness);
+
+        // constant polynomials still get a non-constant blinding polynomial
+        let (_, blinding) = prover
+            .commit_hiding(&Polynomial::from_scalar(y), &mut rng)
+            .unwrap();
+        assert_eq!(blinding.num_coeffs(), 2);
+
+        let plain = test_setup::<16>(&mut rng);
+        assert!(matches!(
+            KZGProver::new(&plain).commit_hiding(&polynomial, &mut rng),
+            Err(KZGError::NoBlindingPowers)
+        ));
+        assert!(!KZGVerifier::new(&plain).unwrap().verify_hiding_eval(
+            (x, y),
+            blinding_eval,
+            &commitment,
+            &witness
+        ));
+    }
+
+    #[test]
+    fn test_validate_blinding_powers() {
+        let mut rng = SmallRng::from_seed(RNG_SEED);
+        let params =
+            test_setup::<8>(&mut rng).with_insecure_blinding_powers(rng.gen::<u64>().into());
+        params.validate().unwrap();
+
+        let mut bad = params.clone();
+        bad.blinding_gs.as_mut().unwrap()[5] = bad.gs[5];
+        assert!(matches!(bad.validate(), Err(KZGError::InvalidParams(_))));
+
+        // h = g isn't hiding at all
+        let same_generator = test_setup::<8>(&mut rng).with_insecure_blinding_powers(Scalar::one());
+        assert!(matches!(
+            same_generator.validate(),
+            Err(KZGError::InvalidParams(_))
+        ));
+    }
+
+    #[test]
+    fn test_multi_poly_witness() {
+        let mut rng = SmallRng::from_seed(RNG_SEED);
+        let params = test_setup::<16>(&mut rng);
+        let (prover, verifier) = test_participants(&params);
+
+        let polynomials = (0..5)
+            .map(|_| random_polynomial(&mut rng, 2, 16))
+            .collect::<Vec<_>>();
+        let polynomials = polynomials.iter().collect::<Vec<_>>();
+        let commitments = polynomials
+            .iter()
+            .map(|p| prover.commit(p).unwrap())
+            .collect::<Vec<_>>();
+        let x: Scalar = rng.gen::<u64>().into();
+
+        // gamma binds the commitments, the point and the evaluations
+        let ys = polynomials.iter().map(|p| p.eval(x)).collect::<Vec<_>>();
+        let mut transcript = Transcript::<transcript::Keccak256>::new(b"melon");
+        commitments
+            .iter()
+            .for_each(|c| transcript.append_point(b"commitment", c));
+        transcript.append_scalar(b"x", &x);
+        ys.iter().for_each(|y| transcript.append_scalar(b"y", y));
+        let gamma: Scalar = transcript.challenge_scalar(b"gamma");
+
+        let (witness, evaluations) = prover
+            .create_multi_poly_witness(&polynomials, x, gamma)
+            .unwrap();
+        assert_eq!(evaluations, ys);
+        assert!(verifier.verify_multi_poly_eval(&commitments, x, &ys, &witness, gamma));
+
+        let mut bad_ys = ys.clone();
+        bad_ys[2] = random_field_elem_neq(bad_ys[2]);
+        assert!(!verifier.verify_multi_poly_eval(&commitments, x, &bad_ys, &witness, gamma));
+
+        let mut swapped = commitments.clone();
+        swapped.swap(0, 1);
+        assert!(!verifier.verify_multi_poly_eval(&swapped, x, &ys, &witness, gamma));
+
+        let gamma_prime = random_field_elem_neq(gamma);
+        assert!(!verifier.verify_multi_poly_eval(&commitments, x, &ys, &witness, gamma_prime));
+        assert!(!verifier.verify_multi_poly_eval(&commitments[..4], x, &ys, &witness, gamma));
+
+        // a single polynomial is just `create_witness`
+        let (single, _) = prover
+            .create_multi_poly_witness(&polynomials[..1], x, gamma)
+            .unwrap();
+        assert_eq!(
+            single,
+            prover.create_witness(polynomials[0], (x, ys[0])).unwrap()
+        );
+
+        assert!(matches!(
+            prover.create_multi_poly_witness(&[], x, gamma),
+            Err(KZGError::NoPolynomial)
+        ));
+    }
+
+    #[test]
+    fn test_prove_at_challenge() {
+        let mut rng = SmallRng::from_seed(RNG_SEED);
+        let params = test_setup::<16>(&mut rng);
+        let (prover, verifier) = test_participants(&params);
+
+        let polynomial = random_polynomial(&mut rng, 2, 16);
+        let commitment = prover.commit(&polynomial).unwrap();
+
+        let mut transcript = Transcript::<transcript::Keccak256>::new(b"melon");
+        let ((x, y), witness) = prover
+            .prove_at_challenge(&polynomial, &commitment, &mut transcript)
+            .unwrap();
+        assert_eq!(y, polynomial.eval(x));
+
+        let mut transcript = Transcript::<transcript::Keccak256>::new(b"melon");
+        assert!(verifier.verify_at_challenge(&commitment, y, &witness, &mut transcript));
+
+        // a different transcript gives a different x
+        let mut transcript = Transcript::<transcript::Keccak256>::new(b"nolem");
+        assert!(!verifier.verify_at_challenge(&commitment, y, &witness, &mut transcript));
+        let mut transcript = Transcript::<transcript::Sha256>::new(b"melon");
+        assert!(!verifier.verify_at_challenge(&commitment, y, &witness, &mut transcript));
+
+        let mut transcript = Transcript::<transcript::Keccak256>::new(b"melon");
+        let y_prime = random_field_elem_neq(y);
+        assert!(!verifier.verify_at_challenge(&commitment, y_prime, &witness, &mut transcript));
+    }
+
+    /// the whole scheme over an arbitrary pairing engine
+    fn check_engine<E: PairingEngine>(rng: &mut SmallRng) {
+        let params = KZGParams::<E>::from_secret(E::Fr::rand(rng), 16);
+        params.validate().unwrap();
+        let prover = KZGProver::new(&params);
+        let verifier = KZGVerifier::new(&params).unwrap();
+
+        let polynomial = Polynomial::new((0..12).map(|_| E::Fr::rand(rng)).collect()).unwrap();
+        let commitment = prover.commit(&polynomial).unwrap();
+        assert!(verifier.verify_poly(&commitment, &polynomial).unwrap());
+
+        let x = E::Fr::rand(rng);
+        let y = polynomial.eval(x);
+        let witness = prover.create_witness(&polynomial, (x, y)).unwrap();
+        assert!(verifier.verify_eval((x, y), &commitment, &witness));
+        assert!(!verifier.verify_eval((x, y + E::Fr::one()), &commitment, &witness));
+
+        let points: Vec<(E::Fr, E::Fr)> = (0..3)
+            .map(|_| E::Fr::rand(rng))
+            .map(|x| (x, polynomial.eval(x)))
+            .collect();
+        let witness = prover.create_batch_witness(&polynomial, &points).unwrap();
+        assert!(verifier.verify_batch_eval(&points, &commitment, &witness));
+
+        let domain = EvaluationDomain::new(8).unwrap();
+        let cache = prover.open_all_on_domain(&polynomial, &domain).unwrap();
+        let (point, witness) = cache.opening(3).unwrap();
+        assert!(verifier.verify_eval(point, &commitment, &witness));
+
+        let lagrange_params = params.clone().with_lagrange_basis(&domain).unwrap();
+        lagrange_params.validate().unwrap();
+        let evals: Vec<E::Fr> = (0..8).map(|_| E::Fr::rand(rng)).collect();
+        assert_eq!(
+            KZGProver::new(&lagrange_params)
+                .commit_evaluations(&evals)
+                .unwrap(),
+            prover
+                .commit(&Polynomial::from_evaluations_over_domain(&evals, &domain).unwrap())
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_generic_over_engines() {
+        let mut rng = SmallRng::from_seed(RNG_SEED);
+        check_engine::<Bn254>(&mut rng);
+        check_engine::<ark_bls12_381::Bls12_381>(&mut rng);
+    }
+
+    #[test]
+    fn test_open_all_on_domain() {
+        let mut rng = SmallRng::from_seed(RNG_SEED);
+        let params = test_setup::<16>(&mut rng);
+
+        let (prover, verifier) = test_participants(&params);
+
+        // fewer coefficients than domain points, exactly as many, and more
+        for (num_coeffs, domain_size) in [(4, 16), (8, 8), (13, 4), (1, 4)] {
+            let polynomial = random_polynomial(&mut rng, num_coeffs, num_coeffs + 1);
+            let commitment = prover.commit(&polynomial).unwrap();
+            let domain = EvaluationDomain::new(domain_size).unwrap();
+
+            let cache = prover.open_all_on_domain(&polynomial, &domain).unwrap();
+            assert_eq!(cache.len(), domain_size);
+
+            for i in 0..domain_size {
+                let (point, witness) = cache.opening(i).unwrap();
+                assert_eq!(point.1, polynomial.eval(point.0));
+                assert_eq!(
+                    witness,
+                    prover.create_witness(&polynomial, point).unwrap(),
+                    "witness {} differs from create_witness for {} coefficients",
+                    i,
+                    num_coeffs
+                );
+                assert_verify_eval(&verifier, point, &commitment, &witness);
+            }
+            assert!(cache.opening(domain_size).is_none());
+        }
+    }
+
+    fn assert_invalid_params(params: &KZGParams, expected: &str) {
+        match params.validate() {
+            Err(KZGError::InvalidParams(reason)) => assert_eq!(reason, expected),
+            res => panic!("expected InvalidParams({}), got {:?}", expected, res),
+        }
+    }
+
+    #[test]
+    fn test_validate_params() {
+        let mut rng = SmallRng::from_seed(RNG_SEED);
+        let params = test_setup::<9>(&mut rng);
+        params.validate().unwrap();
+
+        let mut bad_g1 = params.clone();
+        let g = bad_g1.gs[0];
+        bad_g1.gs[4] += g;
+        assert_invalid_params(&bad_g1, "e(gs[4], hs[0]) != e(gs[3], hs[1])");
+
+        let mut bad_g2 = params.clone();
+        bad_g2.hs[7] = bad_g2.hs[6];
+        assert_invalid_params(&bad_g2, "e(gs[0], hs[7]) != e(gs[1], hs[6])");
+
+        let mut bad_generator = params.clone();
+        bad_generator.gs[0].double_in_place();
+        assert_invalid_params(&bad_generator, "gs[0] is not the G1 generator");
+
+        let mut identity = params.clone();
+        identity.hs[2] = G2Projective::zero();
+        assert_invalid_params(&identity, "hs[2] is not in the prime-order subgroup");
+
+        let mut off_curve = params.clone();
+        let g = G1Affine::from(off_curve.gs[5]);
+        off_curve.gs[5] = G1Affine::new(g.x, g.y + g.y, false).into();
+        assert_invalid_params(&off_curve, "gs[5] is not in the prime-order subgroup");
+
+        let mut too_short = params;
+        too_short.hs.truncate(1);
+        assert!(too_short.validate().is_err());
+        assert!(matches!(
+            KZGVerifier::new(&too_short),
+            Err(KZGError::InvalidParams(_))
+        ));
+    }
+
+    #[test]
+    fn test_batch_eval() {
+        let mut rng = SmallRng::from_seed(RNG_SEED);
+        let params = test_setup::<16>(&mut rng);
+
+        let (prover, verifier) = test_participants(&params);
+
+        let polynomial = random_polynomial(&mut rng, 8, 16);
+        let commitment = prover.commit(&polynomial).unwrap();
+
+        for k in [1, 3, 7, 12] {
+            let mut points: Vec<(Scalar, Scalar)> = (0..k)
+                .map(|_| {
+                    let x: Scalar = rng.gen::<u64>().into();
+                    (x, polynomial.eval(x))
+                })
+                .collect();
+
+            let witness = prover.create_batch_witness(&polynomial, &points).unwrap();
+            assert!(
+                verifier.verify_batch_eval(&points, &commitment, &witness),
+                "verify_batch_eval failed for {} points",
+                k
+            );
+
+            points[0].1 = random_field_elem_neq(points[0].1);
+            assert!(!verifier.verify_batch_eval(&points, &commitment, &witness));
+            assert!(matches!(
+                prover.create_batch_witness(&polynomial, &points),
+                Err(KZGError::PointNotOnPolynomial)
+            ));
+        }
+    }
 }
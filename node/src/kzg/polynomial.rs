@@ -1,16 +1,20 @@
+use super::domain::EvaluationDomain;
+use super::subproduct_tree::SubproductTree;
+use super::KZGError;
 use ark_bn254::Fr as Scalar;
-use ark_ff::{Field, One, Zero};
+use ark_ff::PrimeField;
 use std::cmp::{Eq, PartialEq};
 use std::iter::Iterator;
-use std::ops::{Add, Mul, MulAssign, Sub};
+use std::ops::{Add, Div, Mul, Rem, Sub};
 
+/// a polynomial over the scalar field `F`, BN254's by default
 #[derive(Clone, Debug)]
-pub struct Polynomial {
+pub struct Polynomial<F: PrimeField = Scalar> {
     pub degree: usize,
-    pub coeffs: Vec<Scalar>,
+    pub coeffs: Vec<F>,
 }
 
-impl PartialEq<Polynomial> for Polynomial {
+impl<F: PrimeField> PartialEq<Polynomial<F>> for Polynomial<F> {
     fn eq(&self, other: &Self) -> bool {
         if self.degree() != other.degree() {
             false
@@ -23,58 +27,67 @@ impl PartialEq<Polynomial> for Polynomial {
     }
 }
 
-impl Eq for Polynomial {}
+impl<F: PrimeField> Eq for Polynomial<F> {}
 
-impl Polynomial {
+impl<F: PrimeField> Polynomial<F> {
     pub fn is_zero(&self) -> bool {
-        self.degree() == 0 && self.coeffs[0] == Scalar::zero()
+        self.degree() == 0 && self.coeffs[0] == F::zero()
     }
 
-    pub fn new_zero() -> Polynomial {
+    pub fn new_zero() -> Polynomial<F> {
         Polynomial {
             degree: 0,
-            coeffs: vec![Scalar::zero()],
+            coeffs: vec![F::zero()],
         }
     }
 
-    pub fn from_scalar(scalar: Scalar) -> Polynomial {
+    pub fn from_scalar(scalar: F) -> Polynomial<F> {
         Polynomial {
             degree: 0,
             coeffs: vec![scalar],
         }
     }
 
-    pub fn new_monic_of_degree(degree: usize) -> Polynomial {
+    pub fn new_monic_of_degree(degree: usize) -> Polynomial<F> {
         Polynomial {
             degree,
-            coeffs: vec![Scalar::one(); degree + 1],
+            coeffs: vec![F::one(); degree + 1],
         }
     }
 
-    pub fn new_zero_with_size(cap: usize) -> Polynomial {
+    pub fn new_zero_with_size(cap: usize) -> Polynomial<F> {
         Polynomial {
             degree: 0,
-            coeffs: vec![Scalar::zero(); cap],
+            coeffs: vec![F::zero(); cap],
         }
     }
 
-    pub fn new(coeffs: Vec<Scalar>) -> Polynomial {
+    /// the polynomial with coefficients `coeffs`, lowest degree first. fails if there are none.
+    pub fn new(coeffs: Vec<F>) -> Result<Polynomial<F>, KZGError> {
+        if coeffs.is_empty() {
+            return Err(KZGError::EmptyInput);
+        }
+        Ok(Self::new_nonempty(coeffs))
+    }
+
+    /// `new` for coefficients that are known to be nonempty
+    pub(crate) fn new_nonempty(coeffs: Vec<F>) -> Polynomial<F> {
         // figure out what the initial degree is
         let degree = Self::compute_degree(&coeffs, coeffs.len() - 1);
         Polynomial { degree, coeffs }
     }
 
     /// note: use this carefully, as setting the degree incorrect can lead to the degree being inconsistent
-    pub fn new_from_coeffs(coeffs: Vec<Scalar>, degree: usize) -> Polynomial {
+    pub fn new_from_coeffs(coeffs: Vec<F>, degree: usize) -> Polynomial<F> {
         Polynomial { degree, coeffs }
     }
 
-    pub fn compute_degree(coeffs: &[Scalar], upper_bound: usize) -> usize {
+    pub fn compute_degree(coeffs: &[F], upper_bound: usize) -> usize {
         let mut i = upper_bound;
         loop {
             if i == 0 {
                 break 0;
-            } else if coeffs[i] != Scalar::zero() {
+            } else if coeffs[i] != F::zero() {
                 break i;
             }
 
@@ -87,7 +100,7 @@ impl Polynomial {
         self.degree = degree;
     }
 
-    pub fn lead(&self) -> Scalar {
+    pub fn lead(&self) -> F {
         self.coeffs[self.degree]
     }
 
@@ -99,20 +112,20 @@ impl Polynomial {
         self.degree
     }
 
-    pub fn coeffs(mut self) -> Vec<Scalar> {
+    pub fn coeffs(mut self) -> Vec<F> {
         self.coeffs.truncate(self.num_coeffs());
         self.coeffs
     }
 
-    pub fn slice_coeffs(&self) -> &[Scalar] {
+    pub fn slice_coeffs(&self) -> &[F] {
         &self.coeffs[..self.num_coeffs()]
     }
 
-    pub fn iter_coeffs(&self) -> impl Iterator<Item = &Scalar> {
+    pub fn iter_coeffs(&self) -> impl Iterator<Item = &F> {
         self.coeffs.iter().take(self.num_coeffs())
     }
 
-    pub fn eval(&self, x: Scalar) -> Scalar {
+    pub fn eval(&self, x: F) -> F {
         let mut res = self.coeffs[self.degree()];
 
         for i in (0..self.degree()).rev() {
@@ -123,32 +136,238 @@ impl Polynomial {
         res
     }
 
-    pub fn lagrange_interpolation(xs: &[Scalar], ys: &[Scalar]) -> Polynomial {
-        assert_eq!(xs.len(), ys.len());
+    /// the polynomial of degree < n taking the value ys[i] at xs[i], in O(n^2). fails if the
+    /// lengths differ, there are no points, or two xs are equal.
+    pub fn lagrange_interpolation(xs: &[F], ys: &[F]) -> Result<Polynomial<F>, KZGError> {
+        check_interpolation_points(xs, ys)?;
 
         // Interpolates on the first `i` samples.
         let mut poly = Polynomial::new_from_coeffs(vec![ys[0]], 0);
         // Is zero on the first `i` samples.
-        let mut base = Polynomial::new_from_coeffs(vec![-xs[0], Scalar::one()], 1);
+        let mut base = Polynomial::new_from_coeffs(vec![-xs[0], F::one()], 1);
 
         // We update `base` so that it is always zero on all previous samples, and `poly` so that
         // it has the correct values on the previous samples.
         for (x, y) in xs[1..].iter().zip(ys[1..].iter()) {
             // Scale `base` so that its value at `x` is the difference between `y` and `poly`'s
             // current value at `x`: Adding it to `poly` will then make it correct for `x`.
-            let diff = (*y - poly.eval(*x)) * base.eval(*x).inverse().unwrap();
+            // `base` is zero at every previous x, so this only fails on a duplicate
+            let base_inv = base.eval(*x).inverse().ok_or(KZGError::DuplicatePoints)?;
+            let diff = (*y - poly.eval(*x)) * base_inv;
             base = base * &diff;
             poly = poly + base.clone();
 
             // Finally, multiply `base` by X - x, so that it is zero at `x`, too, now.
-            base = base * Polynomial::new_from_coeffs(vec![-(*x), Scalar::one()], 1);
+            base = base * Polynomial::new_from_coeffs(vec![-(*x), F::one()], 1);
+        }
+        Ok(poly)
+    }
+
+    /// interpolates `evals`, taken as the values at 1, w, w^2, ... of `domain`, in O(n log n).
+    /// missing evaluations are treated as zero, and more evaluations than domain points fail.
+    pub fn from_evaluations_over_domain(
+        evals: &[F],
+        domain: &EvaluationDomain<F>,
+    ) -> Result<Polynomial<F>, KZGError> {
+        Ok(Polynomial::new_nonempty(domain.ifft(evals)?))
+    }
+
+    /// evaluates the polynomial at every point of `domain` in O(n log n)
+    pub fn evaluate_over_domain(&self, domain: &EvaluationDomain<F>) -> Vec<F> {
+        // w^n = 1, so reducing mod X^n - 1 doesn't change the evaluations
+        let mut folded = vec![F::zero(); domain.size().min(self.num_coeffs())];
+        for (i, c) in self.iter_coeffs().enumerate() {
+            folded[i % domain.size()] += c;
+        }
+
+        domain
+            .fft(&folded)
+            .expect("folded to at most the domain size")
+    }
+
+    /// interpolates on arbitrary distinct `xs` in O(n log^2 n) using a subproduct tree.
+    /// produces the same polynomial as `lagrange_interpolation`, and fails on the same inputs.
+    pub fn interpolate_fast(xs: &[F], ys: &[F]) -> Result<Polynomial<F>, KZGError> {
+        check_interpolation_points(xs, ys)?;
+        SubproductTree::new(xs)?.interpolate(ys)
+    }
+
+    /// evaluates the polynomial at every point in `xs` in O(n log^2 n) using a subproduct tree
+    pub fn eval_many(&self, xs: &[F]) -> Vec<F> {
+        if xs.is_empty() {
+            return Vec::new();
+        }
+        // nonempty, so the tree always builds
+        SubproductTree::new(xs)
+            .map(|tree| tree.evaluate(self))
+            .unwrap_or_default()
+    }
+
+    pub fn derivative(&self) -> Polynomial<F> {
+        if self.degree() == 0 {
+            return Polynomial::new_zero();
         }
-        poly
+
+        let coeffs = self
+            .iter_coeffs()
+            .enumerate()
+            .skip(1)
+            .map(|(i, c)| F::from(i as u64) * c)
+            .collect();
+        Polynomial::new_nonempty(coeffs)
+    }
+
+    /// product of `self` and `rhs` computed with an NTT in O(n log n). falls back to schoolbook
+    /// multiplication if the field has no domain large enough for the product.
+    pub fn mul_fft(&self, rhs: &Polynomial<F>) -> Polynomial<F> {
+        if self.is_zero() || rhs.is_zero() {
+            return Polynomial::new_zero();
+        }
+
+        let num_coeffs = self.num_coeffs() + rhs.num_coeffs() - 1;
+        let domain = match EvaluationDomain::new(num_coeffs) {
+            Some(domain) => domain,
+            None => {
+                let mut res = self.clone() * rhs.clone();
+                res.shrink_degree();
+                return res;
+            }
+        };
+
+        let fits = "the domain fits the product";
+        let mut lhs = domain.fft(self.slice_coeffs()).expect(fits);
+        let rhs = domain.fft(rhs.slice_coeffs()).expect(fits);
+        lhs.iter_mut().zip(rhs.iter()).for_each(|(l, r)| *l *= r);
+
+        let mut coeffs = domain.ifft(&lhs).expect(fits);
+        coeffs.truncate(num_coeffs);
+        Polynomial::new_nonempty(coeffs)
+    }
+
+    /// schoolbook multiplication for small operands, NTT otherwise
+    pub(crate) fn mul_fast(&self, rhs: &Polynomial<F>) -> Polynomial<F> {
+        if self.num_coeffs().min(rhs.num_coeffs()) <= FFT_MUL_THRESHOLD {
+            let mut res = self.clone() * rhs.clone();
+            res.shrink_degree();
+            res
+        } else {
+            self.mul_fft(rhs)
+        }
+    }
+
+    /// the first `n` coefficients, i.e. `self` mod X^n
+    fn truncate_to(&self, n: usize) -> Polynomial<F> {
+        Polynomial::new_nonempty(self.iter_coeffs().take(n.max(1)).copied().collect())
+    }
+
+    /// X^d * self(1/X), for d >= degree
+    fn reverse(&self, d: usize) -> Polynomial<F> {
+        let mut coeffs = self.slice_coeffs().to_vec();
+        coeffs.resize(d + 1, F::zero());
+        coeffs.reverse();
+        Polynomial::new_nonempty(coeffs)
+    }
+
+    /// g such that self * g = 1 mod X^n, via Newton iteration. requires a nonzero constant term.
+    fn inverse_mod_x_pow(&self, n: usize) -> Polynomial<F> {
+        let two = Polynomial::from_scalar(2u64.into());
+        let mut inv = Polynomial::from_scalar(self.coeffs[0].inverse().unwrap());
+
+        let mut k = 1;
+        while k < n {
+            k = (2 * k).min(n);
+            // inv <- inv * (2 - self * inv) mod X^k
+            let err = self.truncate_to(k).mul_fast(&inv).truncate_to(k);
+            inv = inv.mul_fast(&(&two - &err)).truncate_to(k);
+        }
+
+        inv
+    }
+
+    /// quotient and remainder of dividing by `divisor`, so that self = quotient * divisor + remainder
+    /// with deg(remainder) < deg(divisor). returns `None` if `divisor` is the zero polynomial.
+    pub fn div_rem(&self, divisor: &Polynomial<F>) -> Option<(Polynomial<F>, Polynomial<F>)> {
+        if divisor.is_zero() {
+            return None;
+        }
+
+        let (n, m) = (self.degree(), divisor.degree());
+        if n < m {
+            return Some((Polynomial::new_zero(), self.clone()));
+        }
+
+        let quotient_len = n - m + 1;
+        if quotient_len.min(divisor.num_coeffs()) > FFT_MUL_THRESHOLD {
+            return Some(self.div_rem_fast(divisor));
+        }
+
+        // schoolbook long division, cancelling the leading term of the remainder each round
+        let lead_inv = divisor.lead().inverse().unwrap();
+        let mut remainder = self.slice_coeffs().to_vec();
+        let mut quotient = vec![F::zero(); quotient_len];
+
+        for i in (0..quotient_len).rev() {
+            let factor = remainder[i + m] * lead_inv;
+            quotient[i] = factor;
+            for (j, d) in divisor.iter_coeffs().enumerate() {
+                remainder[i + j] -= factor * d;
+            }
+        }
+
+        remainder.truncate(m.max(1));
+        Some((
+            Polynomial::new_nonempty(quotient),
+            Polynomial::new_nonempty(remainder),
+        ))
+    }
+
+    /// quotient and remainder of division by a nonzero `divisor` in O(n log n), using
+    /// q = rev(rev(self) / rev(divisor) mod X^(n-m+1))
+    pub(crate) fn div_rem_fast(&self, divisor: &Polynomial<F>) -> (Polynomial<F>, Polynomial<F>) {
+        let (n, m) = (self.degree(), divisor.degree());
+        if n < m {
+            return (Polynomial::new_zero(), self.clone());
+        }
+
+        let quotient_len = n - m + 1;
+        let divisor_rev_inv = divisor.reverse(m).inverse_mod_x_pow(quotient_len);
+        let quotient = self
+            .reverse(n)
+            .truncate_to(quotient_len)
+            .mul_fast(&divisor_rev_inv)
+            .truncate_to(quotient_len)
+            .reverse(n - m);
+
+        let remainder = self - &divisor.mul_fast(&quotient);
+        (quotient, remainder)
     }
 }
 
-impl Add for Polynomial {
-    type Output = Polynomial;
+/// equal lengths, at least one point, and no x twice
+pub(crate) fn check_interpolation_points<F: PrimeField>(
+    xs: &[F],
+    ys: &[F],
+) -> Result<(), KZGError> {
+    if xs.len() != ys.len() {
+        return Err(KZGError::PointMismatch(xs.len(), ys.len()));
+    }
+    if xs.is_empty() {
+        return Err(KZGError::EmptyInput);
+    }
+
+    let mut sorted = xs.to_vec();
+    sorted.sort_unstable();
+    if sorted.windows(2).any(|pair| pair[0] == pair[1]) {
+        return Err(KZGError::DuplicatePoints);
+    }
+    Ok(())
+}
+
+/// below this many coefficients schoolbook multiplication beats the NTT
+const FFT_MUL_THRESHOLD: usize = 32;
+
+impl<F: PrimeField> Add for Polynomial<F> {
+    type Output = Polynomial<F>;
 
     fn add(self, rhs: Self) -> Self::Output {
         let (mut res, shorter) = if rhs.degree() > self.degree() {
@@ -165,10 +384,10 @@ impl Add for Polynomial {
     }
 }
 
-impl<'a> Mul<&'a Scalar> for Polynomial {
-    type Output = Polynomial;
+impl<F: PrimeField> Mul<&F> for Polynomial<F> {
+    type Output = Polynomial<F>;
 
-    fn mul(mut self, rhs: &Scalar) -> Self::Output {
+    fn mul(mut self, rhs: &F) -> Self::Output {
         if rhs.is_zero() {
             return Polynomial::new_zero();
         } else {
@@ -178,13 +397,13 @@ impl<'a> Mul<&'a Scalar> for Polynomial {
     }
 }
 
-impl<'a> Sub for &'a Polynomial {
-    type Output = Polynomial;
+impl<F: PrimeField> Sub for &Polynomial<F> {
+    type Output = Polynomial<F>;
 
     fn sub(self, rhs: Self) -> Self::Output {
         let mut res = self.clone();
         if rhs.num_coeffs() > self.num_coeffs() {
-            res.coeffs.resize(rhs.num_coeffs(), Scalar::zero());
+            res.coeffs.resize(rhs.num_coeffs(), F::zero());
             res.degree = rhs.degree();
         }
 
@@ -197,8 +416,28 @@ impl<'a> Sub for &'a Polynomial {
     }
 }
 
-impl Mul<Polynomial> for Polynomial {
-    type Output = Polynomial;
+impl<F: PrimeField> Div for &Polynomial<F> {
+    type Output = Polynomial<F>;
+
+    /// panics if `rhs` is the zero polynomial
+    fn div(self, rhs: Self) -> Self::Output {
+        let (quotient, _) = self.div_rem(rhs).expect("division by zero polynomial");
+        quotient
+    }
+}
+
+impl<F: PrimeField> Rem for &Polynomial<F> {
+    type Output = Polynomial<F>;
+
+    /// panics if `rhs` is the zero polynomial
+    fn rem(self, rhs: Self) -> Self::Output {
+        let (_, remainder) = self.div_rem(rhs).expect("division by zero polynomial");
+        remainder
+    }
+}
+
+impl<F: PrimeField> Mul<Polynomial<F>> for Polynomial<F> {
+    type Output = Polynomial<F>;
 
     fn mul(self, rhs: Self) -> Self::Output {
         let mut res = Polynomial::new_zero_with_size(self.degree() + rhs.degree() + 1);
@@ -216,6 +455,8 @@ impl Mul<Polynomial> for Polynomial {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ark_bn254::Fq;
+    use ark_ff::{One, Zero};
 
     #[test]
     fn test_eval_basic() {
@@ -227,7 +468,8 @@ mod tests {
             4.into(),
             Scalar::zero(),
             Scalar::one(),
-        ]);
+        ])
+        .unwrap();
 
         // y(0) = 34
         assert_eq!(polynomial.eval(Scalar::zero()), 34.into());
@@ -242,7 +484,8 @@ mod tests {
         let xs: Vec<Scalar> = vec![2].into_iter().map(|x| x.into()).collect();
         let ys: Vec<Scalar> = vec![8].into_iter().map(|x| x.into()).collect();
 
-        let interpolation = Polynomial::lagrange_interpolation(xs.as_slice(), ys.as_slice());
+        let interpolation =
+            Polynomial::lagrange_interpolation(xs.as_slice(), ys.as_slice()).unwrap();
 
         for (&x, &y) in xs.iter().zip(ys.iter()) {
             assert_eq!(interpolation.eval(x), y);
@@ -256,10 +499,120 @@ mod tests {
             .into_iter()
             .map(|x| x.into())
             .collect();
-        let interpolation = Polynomial::lagrange_interpolation(xs.as_slice(), ys.as_slice());
+        let interpolation =
+            Polynomial::lagrange_interpolation(xs.as_slice(), ys.as_slice()).unwrap();
 
         for (&x, &y) in xs.iter().zip(ys.iter()) {
             assert_eq!(interpolation.eval(x), y);
         }
     }
+
+    #[test]
+    fn test_div_rem() {
+        // x^3 - 2x^2 - 4 = (x - 3)(x^2 + x + 3) + 5
+        let dividend = Polynomial::new(vec![
+            -Scalar::from(4u64),
+            Scalar::zero(),
+            -Scalar::from(2u64),
+            Scalar::one(),
+        ])
+        .unwrap();
+        let divisor = Polynomial::new(vec![-Scalar::from(3u64), Scalar::one()]).unwrap();
+
+        let (quotient, remainder) = dividend.div_rem(&divisor).unwrap();
+        assert_eq!(
+            quotient,
+            Polynomial::new(vec![3.into(), 1.into(), 1.into()]).unwrap()
+        );
+        assert_eq!(remainder, Polynomial::from_scalar(5.into()));
+        assert_eq!(&dividend / &divisor, quotient);
+        assert_eq!(&dividend % &divisor, remainder);
+
+        // divisor of higher degree leaves the dividend as the remainder
+        let (quotient, remainder) = divisor.div_rem(&dividend).unwrap();
+        assert!(quotient.is_zero());
+        assert_eq!(remainder, divisor);
+
+        // constant divisors always divide exactly
+        let (quotient, remainder) = dividend
+            .div_rem(&Polynomial::from_scalar(2.into()))
+            .unwrap();
+        assert_eq!(quotient * &Scalar::from(2u64), dividend);
+        assert!(remainder.is_zero());
+
+        assert!(dividend.div_rem(&Polynomial::new_zero()).is_none());
+    }
+
+    #[test]
+    fn test_interpolation_over_domain() {
+        let domain = EvaluationDomain::new(7).unwrap();
+        let xs = domain.elements();
+        let ys: Vec<Scalar> = vec![8, 1, 43, 2, 87, 122, 13, 5]
+            .into_iter()
+            .map(|y| y.into())
+            .collect();
+
+        let interpolation =
+            Polynomial::from_evaluations_over_domain(ys.as_slice(), &domain).unwrap();
+        assert_eq!(
+            interpolation,
+            Polynomial::lagrange_interpolation(xs.as_slice(), ys.as_slice()).unwrap()
+        );
+        assert_eq!(interpolation.evaluate_over_domain(&domain), ys);
+
+        // polynomials with more coefficients than the domain has points are reduced first
+        let big = interpolation.clone() * Polynomial::new_monic_of_degree(9);
+        let big_evals = big.evaluate_over_domain(&domain);
+        for (i, &x) in xs.iter().enumerate() {
+            assert_eq!(big_evals[i], big.eval(x));
+        }
+    }
+
+    #[test]
+    fn test_rejects_bad_input() {
+        assert!(matches!(
+            Polynomial::<Scalar>::new(vec![]),
+            Err(KZGError::EmptyInput)
+        ));
+
+        let xs: Vec<Scalar> = vec![2.into(), 5.into(), 2.into()];
+        let ys: Vec<Scalar> = vec![8.into(), 1.into(), 8.into()];
+        for interpolate in [
+            Polynomial::lagrange_interpolation,
+            Polynomial::interpolate_fast,
+        ] {
+            assert!(matches!(
+                interpolate(&xs, &ys),
+                Err(KZGError::DuplicatePoints)
+            ));
+            assert!(matches!(
+                interpolate(&xs[..2], &ys),
+                Err(KZGError::PointMismatch(2, 3))
+            ));
+            assert!(matches!(interpolate(&[], &[]), Err(KZGError::EmptyInput)));
+        }
+
+        assert!(Polynomial::from_scalar(Scalar::one())
+            .eval_many(&[])
+            .is_empty());
+
+        let domain = EvaluationDomain::new(4).unwrap();
+        assert!(matches!(
+            Polynomial::from_evaluations_over_domain(&[Scalar::one(); 5], &domain),
+            Err(KZGError::DomainTooSmall(5, 4))
+        ));
+    }
+
+    #[test]
+    fn test_mul_fft_without_domain() {
+        // BN254's base field only has a subgroup of order 2, too small for this product
+        let f: Polynomial<Fq> = Polynomial::new(vec![1.into(), 2.into(), 3.into()]).unwrap();
+        let g: Polynomial<Fq> = Polynomial::new(vec![4.into(), 5.into()]).unwrap();
+        assert!(EvaluationDomain::<Fq>::new(f.num_coeffs() + g.num_coeffs() - 1).is_none());
+
+        assert_eq!(
+            f.mul_fft(&g),
+            Polynomial::new(vec![4.into(), 13.into(), 22.into(), 15.into()]).unwrap()
+        );
+    }
 }
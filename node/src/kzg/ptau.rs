@@ -0,0 +1,450 @@
+use super::{KZGError, KZGParams};
+use ark_bn254::{Fq, Fq2, FqParameters, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ff::{BigInteger256, FpParameters, Zero};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+const MAGIC: &[u8; 4] = b"ptau";
+const HEADER_SECTION: u32 = 1;
+const TAU_G1_SECTION: u32 = 2;
+const TAU_G2_SECTION: u32 = 3;
+const ALPHA_TAU_G1_SECTION: u32 = 4;
+/// bytes per base field element
+const N8: u32 = 32;
+
+/// the (offset, size) of every section by type
+type Sections = HashMap<u32, (u64, u64)>;
+
+/// loads powers of tau from a snarkjs `.ptau` file for BN254, keeping the first `num_g1` G1
+/// powers and `num_g2` G2 powers. every point is checked to be reduced, on the curve, and in the
+/// prime-order subgroup; use `KZGParams::validate` to also check that the powers are consistent.
+/// `num_g2` must be at least 2, since verifying needs [1]_2 and [s]_2.
+///
+/// the file is a sequence of `(type: u32, size: u64, data)` sections following a
+/// `"ptau" | version: u32 | num_sections: u32` header. section 1 holds the base field modulus and
+/// the ceremony power, section 2 the 2^(power+1) - 1 G1 powers and section 3 the 2^power G2
+/// powers, with coordinates as little-endian Montgomery-form field elements.
+pub fn load_ptau<R: Read + Seek>(
+    mut reader: R,
+    num_g1: usize,
+    num_g2: usize,
+) -> Result<KZGParams, KZGError> {
+    if num_g2 < 2 {
+        return Err(KZGError::InvalidInput(
+            "at least two G2 powers are required".to_string(),
+        ));
+    }
+
+    let (sections, power) = read_sections(&mut reader)?;
+    let max_g1 = (1usize << (power + 1)) - 1;
+    let max_g2 = 1usize << power;
+    if num_g1 > max_g1 || num_g2 > max_g2 {
+        return Err(invalid(format!(
+            "requested {} G1 and {} G2 powers but the ceremony only has {} and {}",
+            num_g1, num_g2, max_g1, max_g2
+        )));
+    }
+
+    let gs = read_g1_section(&mut reader, &sections, TAU_G1_SECTION, num_g1)?;
+
+    let (g2_offset, g2_size) = section(&sections, TAU_G2_SECTION)?;
+    if g2_size < section_size(num_g2, 4)? {
+        return Err(invalid("truncated G2 section"));
+    }
+    reader.seek(SeekFrom::Start(g2_offset))?;
+    let mut reader_g2 = BufReader::new(&mut reader);
+    let hs = (0..num_g2)
+        .map(|_| read_g2(&mut reader_g2).map(G2Projective::from))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(KZGParams {
+        gs,
+        hs,
+        lagrange_gs: None,
+        blinding_gs: None,
+    })
+}
+
+/// loads the first `num_blinding` powers alpha tau^i g of the ceremony's alphaTauG1 section
+/// (section 4, 2^power points), as `KZGParams::blinding_gs` for hiding commitments. alpha is
+/// destroyed by the ceremony like tau, so h = alpha g is a second generator with unknown discrete
+/// log to g, and its powers use the same tau as the ones from `load_ptau`.
+pub fn load_ptau_blinding_powers<R: Read + Seek>(
+    mut reader: R,
+    num_blinding: usize,
+) -> Result<Vec<G1Projective>, KZGError> {
+    let (sections, power) = read_sections(&mut reader)?;
+    if num_blinding > 1usize << power {
+        return Err(invalid(format!(
+            "requested {} blinding powers but the ceremony only has {}",
+            num_blinding,
+            1usize << power
+        )));
+    }
+
+    read_g1_section(&mut reader, &sections, ALPHA_TAU_G1_SECTION, num_blinding)
+}
+
+/// `load_ptau` on the file at `path`
+pub fn load_ptau_file<P: AsRef<Path>>(
+    path: P,
+    num_g1: usize,
+    num_g2: usize,
+) -> Result<KZGParams, KZGError> {
+    load_ptau(File::open(path)?, num_g1, num_g2)
+}
+
+/// `load_ptau_blinding_powers` on the file at `path`
+pub fn load_ptau_blinding_powers_file<P: AsRef<Path>>(
+    path: P,
+    num_blinding: usize,
+) -> Result<Vec<G1Projective>, KZGError> {
+    load_ptau_blinding_powers(File::open(path)?, num_blinding)
+}
+
+/// the file's sections, and the ceremony power from the header
+fn read_sections<R: Read + Seek>(reader: &mut R) -> Result<(Sections, u32), KZGError> {
+    let mut magic = [0u8; 4];
+    reader.read_exact(&mut magic)?;
+    if &magic != MAGIC {
+        return Err(invalid("missing ptau magic bytes"));
+    }
+
+    let _version = read_u32(reader)?;
+    let num_sections = read_u32(reader)?;
+
+    let start = reader.stream_position()?;
+    let len = reader.seek(SeekFrom::End(0))?;
+    reader.seek(SeekFrom::Start(start))?;
+
+    let mut sections = HashMap::new();
+    for _ in 0..num_sections {
+        let section_type = read_u32(reader)?;
+        let size = read_u64(reader)?;
+        let offset = reader.stream_position()?;
+        let end = offset
+            .checked_add(size)
+            .filter(|end| *end <= len)
+            .ok_or_else(|| invalid(format!("section {} extends past the end", section_type)))?;
+        sections.insert(section_type, (offset, size));
+        reader.seek(SeekFrom::Start(end))?;
+    }
+
+    let (header_offset, _) = section(&sections, HEADER_SECTION)?;
+    reader.seek(SeekFrom::Start(header_offset))?;
+    if read_u32(reader)? != N8 {
+        return Err(invalid("unexpected field element size"));
+    }
+    if read_repr(reader)? != FqParameters::MODULUS {
+        return Err(invalid("not a BN254 ceremony"));
+    }
+    let power = read_u32(reader)?;
+    if power > 28 {
+        return Err(invalid("ceremony power too large"));
+    }
+
+    Ok((sections, power))
+}
+
+fn section(sections: &Sections, section_type: u32) -> Result<(u64, u64), KZGError> {
+    sections
+        .get(&section_type)
+        .copied()
+        .ok_or_else(|| invalid(format!("missing section {}", section_type)))
+}
+
+/// bytes taken by `count` points of `num_fq` base field elements each
+fn section_size(count: usize, num_fq: usize) -> Result<u64, KZGError> {
+    count
+        .checked_mul(num_fq * N8 as usize)
+        .and_then(|size| u64::try_from(size).ok())
+        .ok_or_else(|| invalid(format!("{} points don't fit in a section", count)))
+}
+
+/// the first `count` points of a section of G1 points
+fn read_g1_section<R: Read + Seek>(
+    reader: &mut R,
+    sections: &Sections,
+    section_type: u32,
+    count: usize,
+) -> Result<Vec<G1Projective>, KZGError> {
+    let (offset, size) = section(sections, section_type)?;
+    if size < section_size(count, 2)? {
+        return Err(invalid(format!("truncated section {}", section_type)));
+    }
+    reader.seek(SeekFrom::Start(offset))?;
+    let mut reader = BufReader::new(reader);
+    (0..count)
+        .map(|_| read_g1(&mut reader).map(G1Projective::from))
+        .collect()
+}
+
+fn invalid<S: Into<String>>(reason: S) -> KZGError {
+    KZGError::InvalidPtauFile(reason.into())
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> Result<u32, KZGError> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> Result<u64, KZGError> {
+    let mut buf = [0u8; 8];
+    reader.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+/// 32 little-endian bytes
+fn read_repr<R: Read>(reader: &mut R) -> Result<BigInteger256, KZGError> {
+    let mut limbs = [0u64; 4];
+    for limb in limbs.iter_mut() {
+        *limb = read_u64(reader)?;
+    }
+    Ok(BigInteger256::new(limbs))
+}
+
+fn read_fq<R: Read>(reader: &mut R) -> Result<Fq, KZGError> {
+    let repr = read_repr(reader)?;
+    if repr >= FqParameters::MODULUS {
+        return Err(invalid("field element not reduced"));
+    }
+
+    // already in Montgomery form
+    Ok(Fq::new(repr))
+}
+
+fn read_g1<R: Read>(reader: &mut R) -> Result<G1Affine, KZGError> {
+    let x = read_fq(reader)?;
+    let y = read_fq(reader)?;
+    if x.is_zero() && y.is_zero() {
+        return Err(invalid("G1 power is the point at infinity"));
+    }
+
+    let point = G1Affine::new(x, y, false);
+    if !point.is_on_curve() || !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(invalid("G1 power not in the prime-order subgroup"));
+    }
+    Ok(point)
+}
+
+fn read_g2<R: Read>(reader: &mut R) -> Result<G2Affine, KZGError> {
+    let x = Fq2::new(read_fq(reader)?, read_fq(reader)?);
+    let y = Fq2::new(read_fq(reader)?, read_fq(reader)?);
+    if x.is_zero() && y.is_zero() {
+        return Err(invalid("G2 power is the point at infinity"));
+    }
+
+    let point = G2Affine::new(x, y, false);
+    if !point.is_on_curve() || !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(invalid("G2 power not in the prime-order subgroup"));
+    }
+    Ok(point)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kzg::setup;
+    use ark_bn254::Fr as Scalar;
+    use ark_ec::ProjectiveCurve;
+    use ark_ff::{One, PrimeField};
+    use std::io::Cursor;
+
+    const ALPHA: u64 = 7654321;
+
+    fn write_section(out: &mut Vec<u8>, section_type: u32, data: &[u8]) {
+        out.extend_from_slice(&section_type.to_le_bytes());
+        out.extend_from_slice(&(data.len() as u64).to_le_bytes());
+        out.extend_from_slice(data);
+    }
+
+    fn write_repr(out: &mut Vec<u8>, repr: &BigInteger256) {
+        for limb in repr.0 {
+            out.extend_from_slice(&limb.to_le_bytes());
+        }
+    }
+
+    fn write_fq(out: &mut Vec<u8>, fq: &Fq) {
+        // Montgomery form, like snarkjs
+        write_repr(out, &fq.0);
+    }
+
+    /// a ptau file for `2^power` powers of the secret `s`, with alphaTauG1 for alpha = `ALPHA`
+    /// and an unrelated section in between
+    fn test_ptau(s: Scalar, power: u32) -> Vec<u8> {
+        let params = setup(s, 1 << (power + 1)).with_insecure_blinding_powers(Scalar::from(ALPHA));
+
+        let mut header = Vec::new();
+        header.extend_from_slice(&N8.to_le_bytes());
+        write_repr(&mut header, &FqParameters::MODULUS);
+        header.extend_from_slice(&power.to_le_bytes());
+        header.extend_from_slice(&power.to_le_bytes());
+
+        let mut g1 = Vec::new();
+        for g in params.gs.iter().take((1 << (power + 1)) - 1) {
+            let g = G1Affine::from(*g);
+            write_fq(&mut g1, &g.x);
+            write_fq(&mut g1, &g.y);
+        }
+
+        let mut g2 = Vec::new();
+        for h in params.hs.iter().take(1 << power) {
+            let h = G2Affine::from(*h);
+            for c in [h.x.c0, h.x.c1, h.y.c0, h.y.c1] {
+                write_fq(&mut g2, &c);
+            }
+        }
+
+        let mut alpha_g1 = Vec::new();
+        for g in params.blinding_gs.unwrap().iter().take(1 << power) {
+            let g = G1Affine::from(*g);
+            write_fq(&mut alpha_g1, &g.x);
+            write_fq(&mut alpha_g1, &g.y);
+        }
+
+        let mut out = MAGIC.to_vec();
+        out.extend_from_slice(&1u32.to_le_bytes());
+        out.extend_from_slice(&5u32.to_le_bytes());
+        write_section(&mut out, HEADER_SECTION, &header);
+        write_section(&mut out, 7, &[0u8; 13]);
+        write_section(&mut out, ALPHA_TAU_G1_SECTION, &alpha_g1);
+        write_section(&mut out, TAU_G2_SECTION, &g2);
+        write_section(&mut out, TAU_G1_SECTION, &g1);
+        out
+    }
+
+    #[test]
+    fn test_load_ptau() {
+        let s: Scalar = 1234567u64.into();
+        let file = test_ptau(s, 3);
+
+        let params = load_ptau(Cursor::new(&file), 10, 4).unwrap();
+        let expected = setup(s, 10);
+        assert_eq!(params.gs, expected.gs);
+        assert_eq!(params.hs, expected.hs[..4]);
+
+        let params = load_ptau(Cursor::new(&file), 15, 8).unwrap();
+        assert_eq!(params.gs.len(), 15);
+        assert_eq!(params.hs.len(), 8);
+    }
+
+    #[test]
+    fn test_load_ptau_blinding_powers() {
+        let s: Scalar = 1234567u64.into();
+        let file = test_ptau(s, 3);
+
+        let mut params = load_ptau(Cursor::new(&file), 8, 2).unwrap();
+        let blinding_gs = load_ptau_blinding_powers(Cursor::new(&file), 8).unwrap();
+        assert_eq!(
+            blinding_gs,
+            setup(s, 8)
+                .with_insecure_blinding_powers(Scalar::from(ALPHA))
+                .blinding_gs
+                .unwrap()
+        );
+        params.blinding_gs = Some(blinding_gs);
+        params.validate().unwrap();
+
+        assert!(matches!(
+            load_ptau_blinding_powers(Cursor::new(&file), 9),
+            Err(KZGError::InvalidPtauFile(_))
+        ));
+    }
+
+    #[test]
+    fn test_load_ptau_fixture() {
+        // the secrets `test_vectors/gen_ptau.js` wrote the file with
+        let tau = Scalar::from(0x1234567890abcdefu64);
+        let alpha = Scalar::from(0x2468ace0u64);
+        let file = include_bytes!("test_vectors/pot2_fixture.ptau");
+
+        let mut params = load_ptau(Cursor::new(&file[..]), 7, 4).unwrap();
+        params.validate().unwrap();
+        assert_eq!(params.gs[0], G1Projective::prime_subgroup_generator());
+        assert_eq!(
+            params.gs[1],
+            G1Projective::prime_subgroup_generator().mul(tau.into_repr())
+        );
+        assert_eq!(
+            params.hs[1],
+            G2Projective::prime_subgroup_generator().mul(tau.into_repr())
+        );
+        assert_eq!(params.gs, setup(tau, 7).gs);
+        assert_eq!(params.hs, setup(tau, 4).hs);
+
+        params.blinding_gs = Some(load_ptau_blinding_powers(Cursor::new(&file[..]), 4).unwrap());
+        params.validate().unwrap();
+        assert_eq!(
+            params.blinding_gs.unwrap()[0],
+            G1Projective::prime_subgroup_generator().mul(alpha.into_repr())
+        );
+    }
+
+    #[test]
+    fn test_load_ptau_rejects_bad_files() {
+        let file = test_ptau(Scalar::one() + Scalar::one(), 2);
+
+        let too_many = load_ptau(Cursor::new(&file), 8, 4);
+        assert!(matches!(too_many, Err(KZGError::InvalidPtauFile(_))));
+
+        assert!(matches!(
+            load_ptau(Cursor::new(&file), 4, 1),
+            Err(KZGError::InvalidInput(_))
+        ));
+
+        let mut bad_magic = file.clone();
+        bad_magic[0] = b'x';
+        assert!(matches!(
+            load_ptau(Cursor::new(&bad_magic), 4, 4),
+            Err(KZGError::InvalidPtauFile(_))
+        ));
+
+        // the G1 section comes last; corrupt the y coordinate of the second power
+        let mut off_curve = file.clone();
+        let g1_start = file.len() - 7 * 2 * N8 as usize;
+        off_curve[g1_start + 3 * N8 as usize] ^= 1;
+        assert!(load_ptau(Cursor::new(&off_curve), 1, 4).is_ok());
+        assert!(matches!(
+            load_ptau(Cursor::new(&off_curve), 2, 4),
+            Err(KZGError::InvalidPtauFile(_))
+        ));
+
+        let mut unreduced = file.clone();
+        for b in &mut unreduced[g1_start..g1_start + N8 as usize] {
+            *b = 0xff;
+        }
+        assert!(matches!(
+            load_ptau(Cursor::new(&unreduced), 1, 2),
+            Err(KZGError::InvalidPtauFile(_))
+        ));
+
+        let truncated = &file[..g1_start + N8 as usize];
+        assert!(matches!(
+            load_ptau(Cursor::new(truncated), 1, 2),
+            Err(KZGError::InvalidPtauFile(_))
+        ));
+
+        // a section size that overflows the offset of the next one; the first section's size
+        // follows the 12 byte file header and its 4 byte type
+        let mut huge_section = file.clone();
+        huge_section[16..24].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(matches!(
+            load_ptau(Cursor::new(&huge_section), 1, 2),
+            Err(KZGError::InvalidPtauFile(_))
+        ));
+
+        assert!(matches!(
+            section_size(usize::MAX, 2),
+            Err(KZGError::InvalidPtauFile(_))
+        ));
+        assert!(matches!(
+            section_size(usize::MAX / 64 + 1, 2),
+            Err(KZGError::InvalidPtauFile(_))
+        ));
+        assert_eq!(section_size(7, 2).unwrap(), 7 * 2 * N8 as u64);
+    }
+}
@@ -0,0 +1,327 @@
+use super::domain::EvaluationDomain;
+use super::fk20::OpeningCache;
+use super::polynomial::Polynomial;
+use super::{KZGParams, KZGWitness};
+use ark_ec::PairingEngine;
+use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write};
+use serde::{de, Deserializer, Serializer};
+use std::fmt;
+
+/// serde support for any type with an ark-serialize encoding, for use as
+/// `#[serde(with = "melon::kzg::serialization::canonical")]`, e.g. on a `KZGCommitment` field.
+///
+/// values are written in compressed form: as a 0x-prefixed hex string for human-readable formats
+/// like JSON, and as raw bytes otherwise. deserialization checks that points are on the curve and
+/// in the prime-order subgroup.
+pub mod canonical {
+    use super::*;
+
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: CanonicalSerialize,
+        S: Serializer,
+    {
+        let mut bytes = Vec::with_capacity(value.serialized_size());
+        value
+            .serialize(&mut bytes)
+            .map_err(serde::ser::Error::custom)?;
+
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&format!("0x{}", hex::encode(bytes)))
+        } else {
+            serializer.serialize_bytes(&bytes)
+        }
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: CanonicalDeserialize,
+        D: Deserializer<'de>,
+    {
+        let bytes = if deserializer.is_human_readable() {
+            deserializer.deserialize_str(BytesVisitor)?
+        } else {
+            deserializer.deserialize_bytes(BytesVisitor)?
+        };
+
+        let mut reader = bytes.as_slice();
+        let value = T::deserialize(&mut reader).map_err(de::Error::custom)?;
+        if !reader.is_empty() {
+            return Err(de::Error::custom("trailing bytes after value"));
+        }
+        Ok(value)
+    }
+}
+
+/// accepts hex strings, byte strings, and sequences of bytes
+struct BytesVisitor;
+
+impl<'de> de::Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a 0x-prefixed hex string or a byte array")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        let digits = v
+            .strip_prefix("0x")
+            .ok_or_else(|| E::custom("missing 0x prefix"))?;
+        hex::decode(digits).map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(v.to_vec())
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(v)
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(b) = seq.next_element()? {
+            bytes.push(b);
+        }
+        Ok(bytes)
+    }
+}
+
+macro_rules! impl_serde_via_canonical {
+    ($ty:ty, $param:ident: $bound:path) => {
+        impl<$param: $bound> serde::Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                canonical::serialize(self, serializer)
+            }
+        }
+
+        impl<'de, $param: $bound> serde::Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                canonical::deserialize(deserializer)
+            }
+        }
+    };
+}
+
+impl_serde_via_canonical!(KZGParams<E>, E: PairingEngine);
+impl_serde_via_canonical!(Polynomial<F>, F: PrimeField);
+impl_serde_via_canonical!(OpeningCache<E>, E: PairingEngine);
+
+/// only the `num_coeffs` meaningful coefficients are written, length-prefixed
+impl<F: PrimeField> CanonicalSerialize for Polynomial<F> {
+    fn serialize<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+        (self.num_coeffs() as u64).serialize(&mut writer)?;
+        for coeff in self.iter_coeffs() {
+            coeff.serialize(&mut writer)?;
+        }
+        Ok(())
+    }
+
+    fn serialized_size(&self) -> usize {
+        0u64.serialized_size() + self.num_coeffs() * F::default().serialized_size()
+    }
+}
+
+impl<F: PrimeField> CanonicalDeserialize for Polynomial<F> {
+    fn deserialize<R: Read>(reader: R) -> Result<Self, SerializationError> {
+        let coeffs = Vec::<F>::deserialize(reader)?;
+        Polynomial::new(coeffs).map_err(|_| SerializationError::InvalidData)
+    }
+}
+
+/// the domain is written as its size
+impl<E: PairingEngine> CanonicalSerialize for OpeningCache<E> {
+    fn serialize<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+        (self.domain.size() as u64).serialize(&mut writer)?;
+        self.evaluations.serialize(&mut writer)?;
+        self.witnesses.serialize(&mut writer)
+    }
+
+    fn serialized_size(&self) -> usize {
+        0u64.serialized_size()
+            + self.evaluations.serialized_size()
+            + self.witnesses.serialized_size()
+    }
+
+    fn serialize_uncompressed<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+        (self.domain.size() as u64).serialize_uncompressed(&mut writer)?;
+        self.evaluations.serialize_uncompressed(&mut writer)?;
+        self.witnesses.serialize_uncompressed(&mut writer)
+    }
+
+    fn serialize_unchecked<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+        (self.domain.size() as u64).serialize_unchecked(&mut writer)?;
+        self.evaluations.serialize_unchecked(&mut writer)?;
+        self.witnesses.serialize_unchecked(&mut writer)
+    }
+
+    fn uncompressed_size(&self) -> usize {
+        0u64.uncompressed_size()
+            + self.evaluations.uncompressed_size()
+            + self.witnesses.uncompressed_size()
+    }
+}
+
+impl<E: PairingEngine> OpeningCache<E> {
+    fn from_parts(
+        size: u64,
+        evaluations: Vec<E::Fr>,
+        witnesses: Vec<KZGWitness<E>>,
+    ) -> Result<Self, SerializationError> {
+        let domain = EvaluationDomain::new(size as usize)
+            .filter(|domain| domain.size() as u64 == size)
+            .ok_or(SerializationError::InvalidData)?;
+        if evaluations.len() != domain.size() || witnesses.len() != domain.size() {
+            return Err(SerializationError::InvalidData);
+        }
+
+        Ok(OpeningCache {
+            domain,
+            evaluations,
+            witnesses,
+        })
+    }
+}
+
+impl<E: PairingEngine> CanonicalDeserialize for OpeningCache<E> {
+    fn deserialize<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+        let size = u64::deserialize(&mut reader)?;
+        let evaluations = Vec::deserialize(&mut reader)?;
+        let witnesses = Vec::deserialize(&mut reader)?;
+        OpeningCache::from_parts(size, evaluations, witnesses)
+    }
+
+    fn deserialize_uncompressed<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+        let size = u64::deserialize_uncompressed(&mut reader)?;
+        let evaluations = Vec::deserialize_uncompressed(&mut reader)?;
+        let witnesses = Vec::deserialize_uncompressed(&mut reader)?;
+        OpeningCache::from_parts(size, evaluations, witnesses)
+    }
+
+    fn deserialize_unchecked<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+        let size = u64::deserialize_unchecked(&mut reader)?;
+        let evaluations = Vec::deserialize_unchecked(&mut reader)?;
+        let witnesses = Vec::deserialize_unchecked(&mut reader)?;
+        OpeningCache::from_parts(size, evaluations, witnesses)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kzg::{setup, KZGCommitment, KZGProver};
+    use ark_bn254::{Fq, Fr as Scalar, G1Affine};
+    use ark_ec::AffineCurve;
+    use ark_ff::{One, Zero};
+
+    fn test_params() -> KZGParams {
+        setup(12345u64.into(), 8)
+            .with_lagrange_basis(&EvaluationDomain::new(4).unwrap())
+            .unwrap()
+            .with_insecure_blinding_powers(678u64.into())
+    }
+
+    fn test_polynomial() -> Polynomial {
+        let mut coeffs: Vec<Scalar> = (1..=6u64).map(Scalar::from).collect();
+        // trailing zeros beyond the degree aren't part of the encoding
+        coeffs.extend([Scalar::zero(); 3]);
+        Polynomial::new(coeffs).unwrap()
+    }
+
+    fn roundtrip<T: CanonicalSerialize + CanonicalDeserialize>(value: &T) -> (T, T) {
+        let mut compressed = Vec::new();
+        value.serialize(&mut compressed).unwrap();
+        assert_eq!(compressed.len(), value.serialized_size());
+
+        let mut uncompressed = Vec::new();
+        value.serialize_uncompressed(&mut uncompressed).unwrap();
+        assert_eq!(uncompressed.len(), value.uncompressed_size());
+
+        (
+            T::deserialize(compressed.as_slice()).unwrap(),
+            T::deserialize_uncompressed(uncompressed.as_slice()).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_canonical_roundtrip() {
+        let params = test_params();
+        let (compressed, uncompressed) = roundtrip(&params);
+        assert_eq!(compressed.gs, params.gs);
+        assert_eq!(uncompressed.hs, params.hs);
+        assert_eq!(compressed.lagrange_gs, params.lagrange_gs);
+        assert_eq!(uncompressed.blinding_gs, params.blinding_gs);
+
+        let prover = KZGProver::new(&params);
+        let polynomial = test_polynomial();
+        assert_eq!(
+            roundtrip(&polynomial),
+            (polynomial.clone(), polynomial.clone())
+        );
+
+        let commitment = prover.commit(&polynomial).unwrap();
+        assert_eq!(roundtrip(&commitment), (commitment, commitment));
+
+        let domain = EvaluationDomain::new(8).unwrap();
+        let cache = prover.open_all_on_domain(&polynomial, &domain).unwrap();
+        assert_eq!(roundtrip(&cache), (cache.clone(), cache));
+    }
+
+    #[test]
+    fn test_serde_roundtrip() {
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Bundle {
+            params: KZGParams,
+            polynomial: Polynomial,
+            #[serde(with = "canonical")]
+            commitment: KZGCommitment,
+        }
+
+        let params = test_params();
+        let polynomial = test_polynomial();
+        let commitment = KZGProver::new(&params).commit(&polynomial).unwrap();
+        let bundle = Bundle {
+            params,
+            polynomial,
+            commitment,
+        };
+
+        let json = serde_json::to_string(&bundle).unwrap();
+        let from_json: Bundle = serde_json::from_str(&json).unwrap();
+        let bytes = bincode::serialize(&bundle).unwrap();
+        let from_bincode: Bundle = bincode::deserialize(&bytes).unwrap();
+
+        for decoded in [from_json, from_bincode] {
+            assert_eq!(decoded.params.gs, bundle.params.gs);
+            assert_eq!(decoded.params.hs, bundle.params.hs);
+            assert_eq!(decoded.polynomial, bundle.polynomial);
+            assert_eq!(decoded.commitment, bundle.commitment);
+        }
+    }
+
+    #[test]
+    fn test_rejects_invalid_encodings() {
+        // (1, 2) is the generator, so (1, 3) is off the curve
+        let off_curve = G1Affine::new(Fq::one(), Fq::from(3u64), false);
+        let mut bytes = Vec::new();
+        off_curve.serialize_uncompressed(&mut bytes).unwrap();
+        assert!(G1Affine::deserialize_uncompressed(bytes.as_slice()).is_err());
+
+        // compressed encodings with trailing garbage are rejected by serde
+        let mut bytes = Vec::new();
+        G1Affine::prime_subgroup_generator()
+            .serialize(&mut bytes)
+            .unwrap();
+        bytes.push(0);
+        let json = format!("\"0x{}\"", hex::encode(&bytes));
+        let mut de = serde_json::Deserializer::from_str(&json);
+        assert!(canonical::deserialize::<G1Affine, _>(&mut de).is_err());
+
+        let mut empty = Vec::new();
+        0u64.serialize(&mut empty).unwrap();
+        assert!(Polynomial::<Scalar>::deserialize(empty.as_slice()).is_err());
+
+        assert!(serde_json::from_str::<Polynomial>("\"01\"").is_err());
+    }
+}
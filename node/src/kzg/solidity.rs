@@ -0,0 +1,414 @@
+use super::{KZGError, KZGParams};
+use ark_bn254::{Fq, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ec::ProjectiveCurve;
+use ark_ff::{BigInteger, PrimeField};
+
+const HEADER: &str = "// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+import \"./Pairing.sol\";
+
+/*
+ * Generated by `melon export-solidity`, do not edit by hand.
+ */
+
+contract Constants {
+    using Pairing for *;
+
+    uint256 constant PRIME_Q =
+        21888242871839275222246405745257275088696311157297823662689037894645226208583;
+    uint256 constant BABYJUB_P =
+        21888242871839275222246405745257275088548364400416034343698204186575808495617;
+";
+
+/// the contents of `contracts/src/Constants.sol` for `params`, so that the solidity verifier
+/// uses the same SRS as the prover. the verifier needs `gs` for as many coefficients as it
+/// commits to and `hs[0]`, `hs[1]`, so this fails with fewer than two G2 powers.
+///
+/// G2 coordinates are split into `_0` and `_1` arrays in the order the EVM pairing precompile
+/// takes them, imaginary part first, i.e. `SRS_G2_X_0[i]` is `hs[i].x.c1`.
+pub fn constants_sol(params: &KZGParams) -> Result<String, KZGError> {
+    if params.gs.is_empty() || params.hs.len() < 2 {
+        return Err(KZGError::InvalidParams(
+            "the verifier needs at least one G1 and two G2 powers".to_string(),
+        ));
+    }
+
+    let gs: Vec<G1Affine> = G1Projective::batch_normalization_into_affine(&params.gs);
+    let hs: Vec<G2Affine> = G2Projective::batch_normalization_into_affine(&params.hs);
+
+    let mut out = HEADER.to_string();
+    write_array(&mut out, "SRS_G1_X", gs.iter().map(|g| g.x));
+    write_array(&mut out, "SRS_G1_Y", gs.iter().map(|g| g.y));
+    write_array(&mut out, "SRS_G2_X_0", hs.iter().map(|h| h.x.c1));
+    write_array(&mut out, "SRS_G2_X_1", hs.iter().map(|h| h.x.c0));
+    write_array(&mut out, "SRS_G2_Y_0", hs.iter().map(|h| h.y.c1));
+    write_array(&mut out, "SRS_G2_Y_1", hs.iter().map(|h| h.y.c0));
+    out.push_str("}\n");
+
+    Ok(out)
+}
+
+/// a storage array of uint256, laid out the way `forge fmt` does
+fn write_array(out: &mut String, name: &str, values: impl Iterator<Item = Fq>) {
+    let values = values
+        .map(|value| format!("        uint256(\n            {}\n        )", word(value)))
+        .collect::<Vec<_>>();
+
+    out.push_str(&format!(
+        "\n    uint256[] {} = [\n{}\n    ];\n",
+        name,
+        values.join(",\n")
+    ));
+}
+
+const BATCH_VERIFIER_TEMPLATE: &str = include_str!("templates/BatchVerifier.sol");
+
+/// the contents of `contracts/src/BatchVerifier.sol` for `params`: a verifier for openings from
+/// `KZGProver::create_batch_witness` at up to `num_points` points, with the SRS it needs, `gs`
+/// up to `num_points - 1` and `hs` up to `num_points`, inlined.
+pub fn batch_verifier_sol(params: &KZGParams, num_points: usize) -> Result<String, KZGError> {
+    if num_points == 0 {
+        return Err(KZGError::InvalidInput(
+            "the verifier needs at least one point".to_string(),
+        ));
+    }
+    check_batch_srs(params, num_points)?;
+
+    let gs = G1Projective::batch_normalization_into_affine(&params.gs[..num_points]);
+    let hs = G2Projective::batch_normalization_into_affine(&params.hs[..=num_points]);
+
+    let srs_g1 = gs
+        .iter()
+        .enumerate()
+        .map(|(i, g)| {
+            format!(
+                "        if (i == {}) {{\n            return\n                Pairing.G1Point(\n                    {},\n                    {}\n                );\n        }}\n",
+                i,
+                word(g.x),
+                word(g.y)
+            )
+        })
+        .collect::<String>();
+    let srs_g2 = hs
+        .iter()
+        .enumerate()
+        .map(|(i, h)| {
+            format!(
+                "        if (i == {}) {{\n            return\n                Pairing.G2Point(\n                    [\n                        uint256({}),\n                        {}\n                    ],\n                    [\n                        uint256({}),\n                        {}\n                    ]\n                );\n        }}\n",
+                i,
+                word(h.x.c1),
+                word(h.x.c0),
+                word(h.y.c1),
+                word(h.y.c0)
+            )
+        })
+        .collect::<String>();
+
+    Ok(BATCH_VERIFIER_TEMPLATE
+        .replace("{{NUM_POINTS}}", &num_points.to_string())
+        .replace("{{SRS_G1}}\n", &srs_g1)
+        .replace("{{SRS_G2}}\n", &srs_g2))
+}
+
+/// opening n points takes n powers in G1 and n + 1 in G2
+fn check_batch_srs(params: &KZGParams, num_points: usize) -> Result<(), KZGError> {
+    if params.gs.len() < num_points {
+        return Err(KZGError::SrsTooSmall(num_points, params.gs.len()));
+    }
+    if params.hs.len() < num_points + 1 {
+        return Err(KZGError::SrsTooSmall(num_points + 1, params.hs.len()));
+    }
+    Ok(())
+}
+
+fn word(value: Fq) -> String {
+    format!("0x{}", hex::encode(value.into_repr().to_bytes_be()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kzg::bundle::ProofBundle;
+    use crate::kzg::polynomial::Polynomial;
+    use crate::kzg::{setup, KZGProver};
+    use ark_bn254::{Bn254, Fr as Scalar};
+    use ark_ec::{AffineCurve, PairingEngine};
+    use ark_ff::{Field, One, UniformRand, Zero};
+    use rand::{rngs::SmallRng, SeedableRng};
+
+    const RNG_SEED: [u8; 32] = [69; 32];
+
+    /// a G1 and a G2 point as the pairing precompile takes them
+    type PairingInput = (G1Affine, G2Affine);
+
+    /// the pairs `BatchVerifier.pairingInput` passes to the pairing precompile for `bundle`,
+    /// computed the way the contract does: the vanishing polynomial Z by multiplying in one
+    /// (X - x_i) at a time, the interpolation I as sum_i y_i q_i / q_i(x_i) with q_i = Z / (X - x_i)
+    /// by synthetic division, and then
+    ///     (z_0 W - C + [I(s)]_1, [1]_2), (z_1 W, [s]_2), ..., (z_n W, [s^n]_2)
+    /// empty for duplicate points, like the contract. fails where the contract reverts.
+    fn batch_pairing_input(
+        params: &KZGParams,
+        bundle: &ProofBundle,
+    ) -> Result<Vec<PairingInput>, KZGError> {
+        let witness = bundle
+            .witness
+            .ok_or_else(|| KZGError::InvalidBundle("no opening to verify".to_string()))?;
+        let (xs, ys) = (&bundle.points, &bundle.evaluations);
+        let n = xs.len();
+        if n == 0 {
+            return Err(KZGError::EmptyInput);
+        }
+        if ys.len() != n {
+            return Err(KZGError::PointMismatch(n, ys.len()));
+        }
+        check_batch_srs(params, n)?;
+
+        let mut z = vec![Scalar::zero(); n + 1];
+        z[0] = Scalar::one();
+        for (i, x) in xs.iter().enumerate() {
+            for j in (1..=i + 1).rev() {
+                z[j] = z[j - 1] - *x * z[j];
+            }
+            z[0] = -*x * z[0];
+        }
+
+        let mut interpolation = vec![Scalar::zero(); n];
+        let mut q = vec![Scalar::zero(); n];
+        for (x, y) in xs.iter().zip(ys) {
+            q[n - 1] = z[n];
+            for j in (1..n).rev() {
+                q[j - 1] = z[j] + *x * q[j];
+            }
+
+            let denominator = q.iter().rev().fold(Scalar::zero(), |acc, c| acc * x + c);
+            let inverse = match denominator.inverse() {
+                Some(inverse) => inverse,
+                None => return Ok(vec![]),
+            };
+
+            let scale = *y * inverse;
+            for (coeff, q) in interpolation.iter_mut().zip(&q) {
+                *coeff += scale * q;
+            }
+        }
+
+        let mut lhs = -bundle.commitment.into_projective();
+        for (g, coeff) in params.gs.iter().zip(&interpolation) {
+            lhs += g.mul(coeff.into_repr());
+        }
+        lhs += witness.mul(z[0]);
+
+        let mut input = vec![(lhs.into_affine(), params.hs[0].into_affine())];
+        for (z, h) in z.iter().zip(&params.hs).skip(1) {
+            input.push((witness.mul(*z).into_affine(), h.into_affine()));
+        }
+        Ok(input)
+    }
+
+    /// `BatchVerifier.verifyBatch` on `bundle`, i.e. whether the pairings of
+    /// `batch_pairing_input` multiply to one
+    fn evm_verify_batch(params: &KZGParams, bundle: &ProofBundle) -> Result<bool, KZGError> {
+        let input = batch_pairing_input(params, bundle)?;
+        if input.is_empty() {
+            return Ok(false);
+        }
+
+        let prepared = input
+            .iter()
+            .map(|(g, h)| ((*g).into(), (*h).into()))
+            .collect::<Vec<_>>();
+        Ok(Bn254::product_of_pairings(&prepared).is_one())
+    }
+
+    /// the uint256 words of the precompile input, G2 coordinates imaginary part first
+    fn pairing_input_words(input: &[PairingInput]) -> Vec<Fq> {
+        input
+            .iter()
+            .flat_map(|(g, h)| {
+                let (x, y) = if g.is_zero() {
+                    (Fq::zero(), Fq::zero())
+                } else {
+                    (g.x, g.y)
+                };
+                [x, y, h.x.c1, h.x.c0, h.y.c1, h.y.c0]
+            })
+            .collect()
+    }
+
+    /// the dummy ceremony `BatchVerifier.sol` is generated for. the one in `Constants.sol` has
+    /// secret 1, so every power is the generator, which would hide a power paired with the wrong
+    /// coefficient.
+    fn batch_params() -> KZGParams {
+        setup(12345u64.into(), 8)
+    }
+
+    /// openings of a random polynomial at random points, and whether they hold
+    fn batch_cases(params: &KZGParams, rng: &mut SmallRng) -> Vec<(ProofBundle, bool)> {
+        let prover = KZGProver::new(params);
+        let polynomial = Polynomial::new((0..8).map(|_| Scalar::rand(rng)).collect()).unwrap();
+        let commitment = prover.commit(&polynomial).unwrap();
+        let mut opening = |n: usize| {
+            let points = (0..n)
+                .map(|_| {
+                    let x = Scalar::rand(rng);
+                    (x, polynomial.eval(x))
+                })
+                .collect::<Vec<_>>();
+            let witness = prover.create_batch_witness(&polynomial, &points).unwrap();
+            ProofBundle::new_batch_opening(params, commitment, &points, Some(witness))
+        };
+
+        let mut wrong_value = opening(3);
+        wrong_value.evaluations[1] += Scalar::one();
+        let mut wrong_witness = opening(4);
+        wrong_witness.witness = opening(4).witness;
+
+        vec![
+            (opening(1), true),
+            (opening(2), true),
+            (opening(4), true),
+            (wrong_value, false),
+            (wrong_witness, false),
+        ]
+    }
+
+    fn batch_test_vectors() -> String {
+        let params = batch_params();
+        let cases = batch_cases(&params, &mut SmallRng::from_seed(RNG_SEED))
+            .into_iter()
+            .map(|(bundle, valid)| {
+                let input = batch_pairing_input(&params, &bundle).unwrap();
+                serde_json::json!({
+                    "bundle": bundle,
+                    "valid": valid,
+                    "pairing_input": pairing_input_words(&input)
+                        .into_iter()
+                        .map(word)
+                        .collect::<Vec<_>>(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let vectors = serde_json::json!({ "num_cases": cases.len(), "cases": cases });
+        serde_json::to_string_pretty(&vectors).unwrap() + "\n"
+    }
+
+    #[test]
+    fn test_matches_checked_in_constants() {
+        // the dummy ceremony with secret 1 that the contract tests run against
+        let mut params = setup(Scalar::one(), 129);
+        params.hs.truncate(2);
+
+        assert_eq!(
+            constants_sol(&params).unwrap(),
+            include_str!("../../../contracts/src/Constants.sol")
+        );
+    }
+
+    #[test]
+    fn test_g2_ordering() {
+        let params = setup(12345u64.into(), 3);
+        let constants = constants_sol(&params).unwrap();
+
+        // the G2 generator's x = c0 + c1 * u, as the precompile expects it: [c1, c0]
+        let x_0 = constants.find("SRS_G2_X_0").unwrap();
+        let x_1 = constants.find("SRS_G2_X_1").unwrap();
+        let c1 = constants
+            .find("0x198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2")
+            .unwrap();
+        let c0 = constants
+            .find("0x1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed")
+            .unwrap();
+        assert!(x_0 < c1 && c1 < x_1 && x_1 < c0);
+        assert_eq!(constants.matches("uint256(\n").count(), 3 * 2 + 3 * 4);
+
+        let mut too_few = params;
+        too_few.hs.truncate(1);
+        assert!(matches!(
+            constants_sol(&too_few),
+            Err(KZGError::InvalidParams(_))
+        ));
+    }
+
+    #[test]
+    fn test_matches_checked_in_batch_verifier() {
+        assert_eq!(
+            batch_verifier_sol(&batch_params(), 4).unwrap(),
+            include_str!("../../../contracts/src/BatchVerifier.sol")
+        );
+
+        let params = setup(12345u64.into(), 4);
+        assert!(batch_verifier_sol(&params, 3).is_ok());
+        assert!(matches!(
+            batch_verifier_sol(&params, 4),
+            Err(KZGError::SrsTooSmall(5, 4))
+        ));
+        assert!(batch_verifier_sol(&params, 0).is_err());
+    }
+
+    #[test]
+    fn test_batch_pairing_input() {
+        let params = setup(12345u64.into(), 8);
+        let mut rng = SmallRng::from_seed(RNG_SEED);
+
+        for _ in 0..4 {
+            for (bundle, valid) in batch_cases(&params, &mut rng) {
+                assert_eq!(bundle.verify(&params).unwrap(), valid);
+                assert_eq!(evm_verify_batch(&params, &bundle).unwrap(), valid);
+
+                let input = batch_pairing_input(&params, &bundle).unwrap();
+                assert_eq!(input.len(), bundle.points.len() + 1);
+                assert_eq!(pairing_input_words(&input).len(), 6 * input.len());
+            }
+        }
+
+        // duplicate points give an empty input, which doesn't verify
+        let (mut bundle, _) = batch_cases(&params, &mut rng).swap_remove(1);
+        bundle.points[1] = bundle.points[0];
+        bundle.evaluations[1] = bundle.evaluations[0];
+        assert!(batch_pairing_input(&params, &bundle).unwrap().is_empty());
+        assert!(!evm_verify_batch(&params, &bundle).unwrap());
+        assert!(!bundle.verify(&params).unwrap());
+
+        // n points need [s^n]_2
+        bundle.points = vec![Scalar::one(); 8];
+        bundle.evaluations = vec![Scalar::one(); 8];
+        assert!(matches!(
+            batch_pairing_input(&params, &bundle),
+            Err(KZGError::SrsTooSmall(9, 8))
+        ));
+    }
+
+    #[test]
+    fn test_matches_checked_in_batch_vectors() {
+        // the vectors `contracts/test/BatchVerifier.t.sol` runs the generated contract on.
+        // `MELON_UPDATE_TEST_VECTORS=1 cargo test` rewrites them.
+        let vectors = batch_test_vectors();
+        if std::env::var_os("MELON_UPDATE_TEST_VECTORS").is_some() {
+            std::fs::write(
+                concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/../contracts/test/files/batch_vectors.json"
+                ),
+                &vectors,
+            )
+            .unwrap();
+        }
+
+        assert_eq!(
+            vectors,
+            include_str!("../../../contracts/test/files/batch_vectors.json")
+        );
+
+        let params = batch_params();
+        let vectors: serde_json::Value = serde_json::from_str(&vectors).unwrap();
+        for case in vectors["cases"].as_array().unwrap() {
+            let bundle: ProofBundle = serde_json::from_value(case["bundle"].clone()).unwrap();
+            let valid = case["valid"].as_bool().unwrap();
+            assert_eq!(evm_verify_batch(&params, &bundle).unwrap(), valid);
+            assert_eq!(bundle.verify(&params).unwrap(), valid);
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use super::polynomial::Polynomial;
+use super::KZGError;
+use ark_bn254::Fr as Scalar;
+use ark_ff::PrimeField;
+
+/// binary tree over points x_0, ..., x_(n-1) whose leaves are (X - x_i) and whose inner nodes are
+/// the product of their children, so the root is the vanishing polynomial of all the points.
+/// building it is O(n log^2 n), after which interpolation and multipoint evaluation over the same
+/// points are O(n log^2 n) as well.
+#[derive(Clone, Debug)]
+pub struct SubproductTree<F: PrimeField = Scalar> {
+    poly: Polynomial<F>,
+    children: Option<Box<(SubproductTree<F>, SubproductTree<F>)>>,
+}
+
+impl<F: PrimeField> SubproductTree<F> {
+    /// builds the tree over `xs`, failing if there are none
+    pub fn new(xs: &[F]) -> Result<SubproductTree<F>, KZGError> {
+        if xs.is_empty() {
+            return Err(KZGError::EmptyInput);
+        }
+        Ok(Self::build(xs))
+    }
+
+    fn build(xs: &[F]) -> SubproductTree<F> {
+        if xs.len() == 1 {
+            return SubproductTree {
+                poly: Polynomial::new_from_coeffs(vec![-xs[0], F::one()], 1),
+                children: None,
+            };
+        }
+
+        let (left, right) = xs.split_at(xs.len() / 2);
+        let left = SubproductTree::build(left);
+        let right = SubproductTree::build(right);
+
+        SubproductTree {
+            poly: left.poly.mul_fast(&right.poly),
+            children: Some(Box::new((left, right))),
+        }
+    }
+
+    /// number of points the tree was built over
+    pub fn len(&self) -> usize {
+        self.poly.degree()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// prod (X - x_i)
+    pub fn vanishing_polynomial(&self) -> &Polynomial<F> {
+        &self.poly
+    }
+
+    /// evaluations of `polynomial` at each of the tree's points, in order
+    pub fn evaluate(&self, polynomial: &Polynomial<F>) -> Vec<F> {
+        let mut evals = Vec::with_capacity(self.len());
+        let (_, remainder) = polynomial.div_rem_fast(&self.poly);
+        self.evaluate_remainder(&remainder, &mut evals);
+        evals
+    }
+
+    /// `remainder` has already been reduced mod `self.poly`
+    fn evaluate_remainder(&self, remainder: &Polynomial<F>, evals: &mut Vec<F>) {
+        match &self.children {
+            // p mod (X - x) = p(x)
+            None => evals.push(remainder.coeffs[0]),
+            Some(children) => {
+                for child in [&children.0, &children.1] {
+                    let (_, child_remainder) = remainder.div_rem_fast(&child.poly);
+                    child.evaluate_remainder(&child_remainder, evals);
+                }
+            }
+        }
+    }
+
+    /// the unique polynomial of degree < n taking the value ys[i] at x_i. fails if the tree's
+    /// points aren't distinct.
+    pub fn interpolate(&self, ys: &[F]) -> Result<Polynomial<F>, KZGError> {
+        if ys.len() != self.len() {
+            return Err(KZGError::PointMismatch(self.len(), ys.len()));
+        }
+
+        // with m = prod (X - x_i), p = sum ys[i] / m'(x_i) * m / (X - x_i). m'(x_i) is zero
+        // exactly when x_i is a repeated root.
+        let weights = self
+            .evaluate(&self.poly.derivative())
+            .into_iter()
+            .zip(ys.iter())
+            .map(|(d, y)| d.inverse().map(|d_inv| *y * d_inv))
+            .collect::<Option<Vec<F>>>()
+            .ok_or(KZGError::DuplicatePoints)?;
+
+        let mut interpolation = self.linear_combination(&weights);
+        interpolation.shrink_degree();
+        Ok(interpolation)
+    }
+
+    /// sum weights[i] * m / (X - x_i), combining bottom up
+    fn linear_combination(&self, weights: &[F]) -> Polynomial<F> {
+        match &self.children {
+            None => Polynomial::from_scalar(weights[0]),
+            Some(children) => {
+                let (left, right) = (&children.0, &children.1);
+                let (left_weights, right_weights) = weights.split_at(left.len());
+
+                left.linear_combination(left_weights).mul_fast(&right.poly)
+                    + right.linear_combination(right_weights).mul_fast(&left.poly)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ff::{One, UniformRand, Zero};
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+    const RNG_SEED: [u8; 32] = [69; 32];
+
+    fn random_polynomial(rng: &mut SmallRng, num_coeffs: usize) -> Polynomial {
+        Polynomial::new((0..num_coeffs).map(|_| Scalar::rand(rng)).collect()).unwrap()
+    }
+
+    #[test]
+    fn test_fast_arithmetic() {
+        let mut rng = SmallRng::from_seed(RNG_SEED);
+
+        for _ in 0..20 {
+            let (f_len, g_len) = (rng.gen_range(1..150), rng.gen_range(1..150));
+            let f = random_polynomial(&mut rng, f_len);
+            let g = random_polynomial(&mut rng, g_len);
+
+            let product = f.mul_fft(&g);
+            assert_eq!(product, f.clone() * g.clone());
+
+            let (q, r) = product.div_rem_fast(&g);
+            assert_eq!(q, f);
+            assert!(r.is_zero());
+
+            let h = random_polynomial(&mut rng, g.num_coeffs());
+            let (q, r) = (product.clone() + h.clone()).div_rem_fast(&g);
+            assert!(r.degree() < g.degree() || (g.degree() == 0 && r.is_zero()));
+            assert_eq!(q.mul_fft(&g) + r, product + h);
+        }
+    }
+
+    #[test]
+    fn test_matches_naive() {
+        let mut rng = SmallRng::from_seed(RNG_SEED);
+
+        for n in [1, 2, 3, 17, 64, 100] {
+            let xs: Vec<Scalar> = (0..n).map(|_| Scalar::rand(&mut rng)).collect();
+            let ys: Vec<Scalar> = (0..n).map(|_| Scalar::rand(&mut rng)).collect();
+
+            let interpolation = Polynomial::interpolate_fast(&xs, &ys).unwrap();
+            assert_eq!(
+                interpolation,
+                Polynomial::lagrange_interpolation(&xs, &ys).unwrap(),
+                "interpolation on {} points differs from lagrange_interpolation",
+                n
+            );
+
+            let num_coeffs = rng.gen_range(1..2 * n + 2);
+            let polynomial = random_polynomial(&mut rng, num_coeffs);
+            let evals = polynomial.eval_many(&xs);
+            for (x, y) in xs.iter().zip(evals) {
+                assert_eq!(polynomial.eval(*x), y);
+            }
+        }
+    }
+
+    #[test]
+    fn test_vanishing_polynomial() {
+        let xs: Vec<Scalar> = vec![2, 5, 7, 90, 111]
+            .into_iter()
+            .map(|x| x.into())
+            .collect();
+        let tree = SubproductTree::new(&xs).unwrap();
+
+        assert_eq!(tree.len(), 5);
+        assert_eq!(tree.vanishing_polynomial().lead(), Scalar::one());
+        for x in xs {
+            assert!(tree.vanishing_polynomial().eval(x).is_zero());
+        }
+    }
+
+    #[test]
+    fn test_rejects_bad_points() {
+        assert!(matches!(
+            SubproductTree::<Scalar>::new(&[]),
+            Err(KZGError::EmptyInput)
+        ));
+
+        let xs: Vec<Scalar> = vec![3u64.into(), 4u64.into(), 3u64.into()];
+        let tree = SubproductTree::new(&xs).unwrap();
+        assert!(matches!(
+            tree.interpolate(&[Scalar::one(); 3]),
+            Err(KZGError::DuplicatePoints)
+        ));
+        assert!(matches!(
+            tree.interpolate(&[Scalar::one(); 2]),
+            Err(KZGError::PointMismatch(3, 2))
+        ));
+    }
+}
@@ -0,0 +1,165 @@
+// Writes pot2_fixture.ptau: a power-2 BN254 ceremony for known tau, alpha and beta, laid out
+// like snarkjs' `powersoftau new` / `contribute` output (binfileutils sections 1-7, points as
+// little-endian Montgomery-form coordinates, G2 coordinates as c0 then c1).
+//
+// It uses its own BigInt curve arithmetic rather than the crate's, so the Rust test loading it
+// checks the reader against an independent writer. Run with `node gen_ptau.js`.
+
+const fs = require("fs");
+const path = require("path");
+
+const q = 21888242871839275222246405745257275088696311157297823662689037894645226208583n;
+const r = 21888242871839275222246405745257275088548364400416034343698204186575808495617n;
+const n8 = 32;
+const R = (1n << 256n) % q;
+
+const TAU = 0x1234567890abcdefn;
+const ALPHA = 0x2468ace0n;
+const BETA = 0x13579bdfn;
+const POWER = 2;
+
+const mod = (a) => ((a % q) + q) % q;
+const pow = (a, e) => {
+  let result = 1n;
+  a = mod(a);
+  for (; e > 0n; e >>= 1n) {
+    if (e & 1n) result = (result * a) % q;
+    a = (a * a) % q;
+  }
+  return result;
+};
+const inv = (a) => pow(a, q - 2n);
+
+// Fq2 = Fq[u] / (u^2 + 1), as [c0, c1]
+const Fq = {
+  zero: 0n,
+  one: 1n,
+  add: (a, b) => mod(a + b),
+  sub: (a, b) => mod(a - b),
+  mul: (a, b) => mod(a * b),
+  inv,
+  eq: (a, b) => a === b,
+  isZero: (a) => a === 0n,
+};
+const Fq2 = {
+  zero: [0n, 0n],
+  one: [1n, 0n],
+  add: (a, b) => [mod(a[0] + b[0]), mod(a[1] + b[1])],
+  sub: (a, b) => [mod(a[0] - b[0]), mod(a[1] - b[1])],
+  mul: (a, b) => [mod(a[0] * b[0] - a[1] * b[1]), mod(a[0] * b[1] + a[1] * b[0])],
+  inv: (a) => {
+    const t = inv(mod(a[0] * a[0] + a[1] * a[1]));
+    return [mod(a[0] * t), mod(-a[1] * t)];
+  },
+  eq: (a, b) => a[0] === b[0] && a[1] === b[1],
+  isZero: (a) => a[0] === 0n && a[1] === 0n,
+};
+
+// affine short Weierstrass y^2 = x^3 + b arithmetic, null is the point at infinity
+function curve(F, b) {
+  const add = (p, s) => {
+    if (p === null) return s;
+    if (s === null) return p;
+    let lambda;
+    if (F.eq(p.x, s.x)) {
+      if (!F.eq(p.y, s.y) || F.isZero(p.y)) return null;
+      const x2 = F.mul(p.x, p.x);
+      lambda = F.mul(F.add(F.add(x2, x2), x2), F.inv(F.add(p.y, p.y)));
+    } else {
+      lambda = F.mul(F.sub(s.y, p.y), F.inv(F.sub(s.x, p.x)));
+    }
+    const x = F.sub(F.sub(F.mul(lambda, lambda), p.x), s.x);
+    return { x, y: F.sub(F.mul(lambda, F.sub(p.x, x)), p.y) };
+  };
+  const mulScalar = (p, k) => {
+    let result = null;
+    for (k = ((k % r) + r) % r; k > 0n; k >>= 1n) {
+      if (k & 1n) result = add(result, p);
+      p = add(p, p);
+    }
+    return result;
+  };
+  const onCurve = (p) =>
+    F.eq(F.mul(p.y, p.y), F.add(F.mul(F.mul(p.x, p.x), p.x), b));
+  return { add, mulScalar, onCurve };
+}
+
+const G1 = curve(Fq, 3n);
+// b' = 3 / (9 + u)
+const G2 = curve(Fq2, Fq2.mul([3n, 0n], Fq2.inv([9n, 1n])));
+
+const g1 = { x: 1n, y: 2n };
+const g2 = {
+  x: [
+    10857046999023057135944570762232829481370756359578518086990519993285655852781n,
+    11559732032986387107991004021392285783925812861821192530917403151452391805634n,
+  ],
+  y: [
+    8495653923123431417604973247489272438418190587263600148770280649306958101930n,
+    4082367875863433681332203403145435568316851327593401208105741076214120093531n,
+  ],
+};
+if (!G1.onCurve(g1) || !G2.onCurve(g2)) throw new Error("generator not on the curve");
+
+// ffjavascript's toRprLEM: x R mod q as n8 little-endian bytes
+function fqBytes(a) {
+  let m = (a * R) % q;
+  const bytes = Buffer.alloc(n8);
+  for (let i = 0; i < n8; i++) {
+    bytes[i] = Number(m & 0xffn);
+    m >>= 8n;
+  }
+  return bytes;
+}
+const g1Bytes = (p) => Buffer.concat([fqBytes(p.x), fqBytes(p.y)]);
+const g2Bytes = (p) =>
+  Buffer.concat([fqBytes(p.x[0]), fqBytes(p.x[1]), fqBytes(p.y[0]), fqBytes(p.y[1])]);
+
+function u32(v) {
+  const b = Buffer.alloc(4);
+  b.writeUInt32LE(v);
+  return b;
+}
+function u64(v) {
+  const b = Buffer.alloc(8);
+  b.writeBigUInt64LE(BigInt(v));
+  return b;
+}
+function bigLE(v) {
+  const bytes = Buffer.alloc(n8);
+  for (let i = 0; i < n8; i++) {
+    bytes[i] = Number(v & 0xffn);
+    v >>= 8n;
+  }
+  return bytes;
+}
+const section = (type, data) => Buffer.concat([u32(type), u64(data.length), data]);
+
+// [c tau^i] for i < count, in the group given by `curve` and `generator`
+function powers(curve, generator, c, count, toBytes) {
+  const out = [];
+  let scalar = c % r;
+  for (let i = 0; i < count; i++) {
+    out.push(toBytes(curve.mulScalar(generator, scalar)));
+    scalar = (scalar * TAU) % r;
+  }
+  return Buffer.concat(out);
+}
+
+const numG1 = 2 ** (POWER + 1) - 1;
+const numG2 = 2 ** POWER;
+const file = Buffer.concat([
+  Buffer.from("ptau"),
+  u32(1),
+  u32(7),
+  section(1, Buffer.concat([u32(n8), bigLE(q), u32(POWER), u32(POWER)])),
+  section(2, powers(G1, g1, 1n, numG1, g1Bytes)),
+  section(3, powers(G2, g2, 1n, numG2, g2Bytes)),
+  section(4, powers(G1, g1, ALPHA, numG2, g1Bytes)),
+  section(5, powers(G1, g1, BETA, numG2, g1Bytes)),
+  section(6, g2Bytes(G2.mulScalar(g2, BETA))),
+  // no recorded contributions
+  section(7, u32(0)),
+]);
+
+fs.writeFileSync(path.join(__dirname, "pot2_fixture.ptau"), file);
@@ -0,0 +1,153 @@
+use ark_ec::{models::SWModelParameters, short_weierstrass_jacobian::GroupAffine};
+use ark_ff::{BigInteger, PrimeField};
+use sha2::Sha256 as Sha2_256;
+use sha3::{Digest, Keccak256 as Sha3Keccak256};
+use std::marker::PhantomData;
+
+/// a 32-byte hash for `Transcript`
+pub trait TranscriptHash {
+    fn hash(data: &[u8]) -> [u8; 32];
+}
+
+/// keccak256, as in solidity
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Keccak256;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Sha256;
+
+impl TranscriptHash for Keccak256 {
+    fn hash(data: &[u8]) -> [u8; 32] {
+        Sha3Keccak256::digest(data).into()
+    }
+}
+
+impl TranscriptHash for Sha256 {
+    fn hash(data: &[u8]) -> [u8; 32] {
+        Sha2_256::digest(data).into()
+    }
+}
+
+/// how a point is absorbed: big-endian x | y with the identity as all zeros, like the EVM
+/// precompiles
+pub trait TranscriptPoint {
+    fn to_transcript_bytes(&self) -> Vec<u8>;
+}
+
+impl<P: SWModelParameters> TranscriptPoint for GroupAffine<P>
+where
+    P::BaseField: PrimeField,
+{
+    fn to_transcript_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.x.into_repr().to_bytes_be();
+        bytes.extend(self.y.into_repr().to_bytes_be());
+        if self.infinity {
+            bytes.iter_mut().for_each(|b| *b = 0);
+        }
+        bytes
+    }
+}
+
+/// a Fiat-Shamir transcript, so the prover can derive verifier challenges itself. the state is a
+/// byte string starting with a domain separator; messages are appended as
+///     label | len(message) as u32 big-endian | message
+/// and a challenge is H(state | label) reduced mod the scalar field, which then replaces the
+/// state. scalars are 32 big-endian bytes, so with `Keccak256` solidity reproduces a challenge with
+/// `uint256(keccak256(abi.encodePacked(...))) % r`.
+#[derive(Clone, Debug)]
+pub struct Transcript<H: TranscriptHash = Keccak256> {
+    state: Vec<u8>,
+    _hash: PhantomData<H>,
+}
+
+impl<H: TranscriptHash> Transcript<H> {
+    pub fn new(domain_separator: &[u8]) -> Self {
+        Transcript {
+            state: domain_separator.to_vec(),
+            _hash: PhantomData,
+        }
+    }
+
+    pub fn append_message(&mut self, label: &[u8], message: &[u8]) {
+        self.state.extend_from_slice(label);
+        self.state
+            .extend_from_slice(&(message.len() as u32).to_be_bytes());
+        self.state.extend_from_slice(message);
+    }
+
+    pub fn append_scalar<F: PrimeField>(&mut self, label: &[u8], scalar: &F) {
+        self.append_message(label, &scalar.into_repr().to_bytes_be());
+    }
+
+    pub fn append_point<P: TranscriptPoint>(&mut self, label: &[u8], point: &P) {
+        self.append_message(label, &point.to_transcript_bytes());
+    }
+
+    pub fn challenge_scalar<F: PrimeField>(&mut self, label: &[u8]) -> F {
+        self.state.extend_from_slice(label);
+        let hash = H::hash(&self.state);
+        self.state = hash.to_vec();
+        F::from_be_bytes_mod_order(&hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{Fr as Scalar, G1Affine};
+    use ark_ec::AffineCurve;
+    use ark_ff::Zero;
+
+    #[test]
+    fn test_challenges() {
+        let point = G1Affine::prime_subgroup_generator();
+        let challenge = |separator: &[u8], y: Scalar| {
+            let mut transcript = Transcript::<Keccak256>::new(separator);
+            transcript.append_point(b"commitment", &point);
+            transcript.append_scalar(b"y", &y);
+            transcript.challenge_scalar::<Scalar>(b"x")
+        };
+
+        let x = challenge(b"melon", Scalar::from(3u64));
+        assert_eq!(x, challenge(b"melon", Scalar::from(3u64)));
+        assert_ne!(x, challenge(b"melon", Scalar::from(4u64)));
+        assert_ne!(x, challenge(b"nolem", Scalar::from(3u64)));
+
+        // keccak256(abi.encodePacked("melon", "commitment", uint32(64), uint256(1), uint256(2),
+        //     "y", uint32(32), uint256(3), "x")) % r
+        let mut preimage = b"melon".to_vec();
+        preimage.extend_from_slice(b"commitment");
+        preimage.extend_from_slice(&64u32.to_be_bytes());
+        preimage.extend_from_slice(&[[0u8; 31].as_slice(), &[1]].concat());
+        preimage.extend_from_slice(&[[0u8; 31].as_slice(), &[2]].concat());
+        preimage.extend_from_slice(b"y");
+        preimage.extend_from_slice(&32u32.to_be_bytes());
+        preimage.extend_from_slice(&[[0u8; 31].as_slice(), &[3]].concat());
+        preimage.extend_from_slice(b"x");
+        assert_eq!(
+            x,
+            Scalar::from_be_bytes_mod_order(&Keccak256::hash(&preimage))
+        );
+
+        // successive challenges differ, and so do the hashes
+        let mut transcript = Transcript::<Keccak256>::new(b"melon");
+        let first: Scalar = transcript.challenge_scalar(b"x");
+        let second: Scalar = transcript.challenge_scalar(b"x");
+        assert_ne!(first, second);
+
+        let mut transcript = Transcript::<Sha256>::new(b"melon");
+        assert_ne!(transcript.challenge_scalar::<Scalar>(b"x"), first);
+        assert!(!first.is_zero());
+    }
+
+    #[test]
+    fn test_point_encoding() {
+        assert_eq!(G1Affine::zero().to_transcript_bytes(), vec![0u8; 64]);
+        assert_eq!(
+            ark_bls12_381::G1Affine::prime_subgroup_generator()
+                .to_transcript_bytes()
+                .len(),
+            96
+        );
+    }
+}